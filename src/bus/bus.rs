@@ -1,48 +1,307 @@
-use crate::cpu::cpu_6502::Cpu6502;
+use crate::bus::mapping::{DeviceHandle, Mapping};
+use crate::bus::ram::Ram;
+use crate::cartridge::cartridge::Cartridge;
+use crate::traits::cpu_bus::CpuBus;
+use crate::traits::device::Device;
 use crate::traits::read::Read;
 use crate::traits::write::Write;
-use std::cell::RefCell;
+use std::ops::RangeInclusive;
 
-#[derive(Debug)]
-pub struct Bus<'a> {
-    cpu: RefCell<Option<&'a Cpu6502<'a>>>,
-    ram: RefCell<[u8; 8192]>
+/// Devices able to pull the shared /IRQ line low. The line stays asserted while any of them
+/// holds it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptSource {
+    FrameCounter = 0x01,
+    Dmc = 0x02,
+    Mapper = 0x04,
+    External = 0x08,
 }
 
-impl<'a> Bus<'a>{
+const RAM_SIZE: usize = 0x0800;
+const CARTRIDGE_START: u16 = 0x4020;
+
+/// The CPU address space, made up of devices mapped into address ranges. `Bus::new` wires up
+/// the NES layout:
+///
+/// | Range         | Device                                        |
+/// |---------------|-----------------------------------------------|
+/// | $0000-$1FFF   | 2KB internal RAM, mirrored every $0800        |
+/// | $2000-$3FFF   | PPU registers $2000-$2007, mirrored every 8   |
+/// | $4000-$401F   | APU and I/O registers                         |
+/// | $4020-$FFFF   | Cartridge                                     |
+///
+/// Only the RAM is present to begin with; the rest is attached as it is created, and anything
+/// else can be mapped on top with `map`. Where ranges overlap, the most recently mapped device
+/// decodes the access. The cartridge is not a mapping of its own, since the PPU needs it too,
+/// and answers whatever in $4020-$FFFF no mapped device decodes.
+///
+/// The bus keeps a latch of the last value on the data bus, which every read and write updates,
+/// dummy accesses included. Reads that nothing drives, because no device is mapped or the device
+/// does not decode the address, return the latch, as do the bits a device leaves undriven.
+/// Writes that nothing decodes are dropped. The NES bus therefore never faults: every read
+/// returns a value and the CPU never stops with `UnmappedRead` or `BusFault` on it.
+#[derive(Debug, Clone)]
+pub struct Bus {
+    mappings: Vec<Mapping>,
+    next_handle: usize,
+    ppu_registers: Option<DeviceHandle>,
+    apu_io: Option<DeviceHandle>,
+    cartridge: Option<Cartridge>,
+    open_bus: u8,
+    irq_sources: u8,
+    nmi_line: bool
+}
+
+impl Bus{
 
     pub fn new() -> Self {
+        let mut bus = Bus::empty();
+        bus.map(0x0000..=0x1FFF, (RAM_SIZE - 1) as u16, Box::new(Ram::new(RAM_SIZE)));
+        bus
+    }
+
+    /// A bus with nothing mapped, for building a machine other than the NES.
+    pub fn empty() -> Self {
         Bus {
-            cpu: RefCell::new(None),
-            ram: RefCell::new([0; 8192])
+            mappings: Vec::new(),
+            next_handle: 0,
+            ppu_registers: None,
+            apu_io: None,
+            cartridge: None,
+            open_bus: 0,
+            irq_sources: 0,
+            nmi_line: false
+        }
+    }
+
+    /// Maps `device` into `range`. The device sees each address in the range ANDed with
+    /// `mask`; pass $FFFF for a device that decodes every address line.
+    pub fn map(&mut self, range: RangeInclusive<u16>, mask: u16, device: Box<dyn Device>) -> DeviceHandle {
+        let mapping = Mapping::new(self.next_handle, range, mask, device);
+        let handle = mapping.handle;
+        self.next_handle += 1;
+        self.mappings.push(mapping);
+        handle
+    }
+
+    /// Removes a device from the bus and hands it back, or `None` if it was already unmapped.
+    pub fn unmap(&mut self, handle: DeviceHandle) -> Option<Box<dyn Device>> {
+        let index = self.mappings.iter().position(|mapping| mapping.handle == handle)?;
+        Some(self.mappings.remove(index).device)
+    }
+
+    /// Connects the PPU. It sees every access to $2000-$3FFF folded onto $2000-$2007. Returns
+    /// the PPU previously attached, if any.
+    pub fn attach_ppu_registers(&mut self, ppu_registers: Box<dyn Device>) -> Option<Box<dyn Device>> {
+        let previous = self.ppu_registers.take().and_then(|handle| self.unmap(handle));
+        self.ppu_registers = Some(self.map(0x2000..=0x3FFF, 0x2007, ppu_registers));
+        previous
+    }
+
+    /// Connects the APU and I/O registers, which see their own addresses $4000-$401F. Returns
+    /// the device previously attached, if any.
+    pub fn attach_apu_io(&mut self, apu_io: Box<dyn Device>) -> Option<Box<dyn Device>> {
+        let previous = self.apu_io.take().and_then(|handle| self.unmap(handle));
+        self.apu_io = Some(self.map(0x4000..=0x401F, 0xFFFF, apu_io));
+        previous
+    }
+
+    /// Plugs in a cartridge, which sees its own addresses $4020-$FFFF. Returns the cartridge
+    /// previously inserted, if any.
+    pub fn insert_cartridge(&mut self, cartridge: Cartridge) -> Option<Cartridge> {
+        self.cartridge.replace(cartridge)
+    }
+
+    pub fn remove_cartridge(&mut self) -> Option<Cartridge> {
+        self.cartridge.take()
+    }
+
+    pub fn cartridge(&self) -> Option<&Cartridge> {
+        self.cartridge.as_ref()
+    }
+
+    /// The cartridge, for the PPU to reach the pattern tables through.
+    pub fn cartridge_mut(&mut self) -> Option<&mut Cartridge> {
+        self.cartridge.as_mut()
+    }
+
+    /// The last value driven onto the data bus, which is what reads of undriven addresses see.
+    pub fn open_bus(&self) -> u8 {
+        self.open_bus
+    }
+
+    /// The value a read of `address` would return, without updating the open bus latch or
+    /// causing side effects on the device. Undriven addresses and bits show the latch as it
+    /// stands, so there is always a value.
+    pub fn peek(&self, address: u16) -> u8 {
+        self.device(address)
+            .and_then(|(device, address)| {
+                let data = device.read_only(address)?;
+                Some(drive(data, device.driven_bits(address), self.open_bus))
+            })
+            .unwrap_or(self.open_bus)
+    }
+
+    /// The mapping decoding `address`, and the address its device sees.
+    fn mapping(&self, address: u16) -> Option<(usize, u16)> {
+        self.mappings.iter().enumerate().rev()
+            .find_map(|(index, mapping)| mapping.decode(address).map(|address| (index, address)))
+    }
+
+    /// The device decoding `address`, and the address it sees.
+    fn device(&self, address: u16) -> Option<(&dyn Device, u16)> {
+        match self.mapping(address) {
+            Some((index, address)) => Some((self.mappings[index].device.as_ref(), address)),
+            None if address >= CARTRIDGE_START => {
+                self.cartridge.as_ref().map(|cartridge| (cartridge as &dyn Device, address))
+            },
+            None => None,
+        }
+    }
+
+    fn device_mut(&mut self, address: u16) -> Option<(&mut dyn Device, u16)> {
+        match self.mapping(address) {
+            Some((index, address)) => Some((self.mappings[index].device.as_mut(), address)),
+            None if address >= CARTRIDGE_START => {
+                self.cartridge.as_mut().map(|cartridge| (cartridge as &mut dyn Device, address))
+            },
+            None => None,
+        }
+    }
+
+    /// Level-triggered: the CPU keeps taking IRQs while the line is held and I is clear.
+    pub fn set_irq_line(&mut self, source: InterruptSource, asserted: bool) {
+        if asserted {
+            self.irq_sources |= source as u8;
+        } else {
+            self.irq_sources &= !(source as u8);
         }
     }
 
-    pub fn attach_cpu(&self, cpu: &'a Cpu6502<'a>) {
-        self.cpu.replace(Some(cpu));
+    /// True while any device, or the cartridge's mapper, holds /IRQ low.
+    pub fn irq_line(&self) -> bool {
+        self.irq_sources != 0 || self.cartridge.as_ref().is_some_and(Cartridge::irq_line)
+    }
+
+    /// Edge-triggered: the CPU latches one NMI each time the line goes from released to asserted.
+    pub fn set_nmi_line(&mut self, asserted: bool) {
+        self.nmi_line = asserted;
+    }
+
+    pub fn nmi_line(&self) -> bool {
+        self.nmi_line
     }
 }
 
-impl Read<u16, u8> for Bus<'_> {
-    fn read(&self, address: u16) -> Option<u8>{
-        if address >= 0x0000 && address <= 0xFFFF {
-            return Some(self.ram.borrow()[address as usize]);
+impl Default for Bus {
+    fn default() -> Self {
+        Bus::new()
+    }
+}
+
+impl CpuBus for Bus {
+    fn irq_line(&self) -> bool {
+        Bus::irq_line(self)
+    }
+
+    fn nmi_line(&self) -> bool {
+        Bus::nmi_line(self)
+    }
+
+    fn tick(&mut self) {
+        if let Some(cartridge) = self.cartridge.as_mut() {
+            cartridge.notify_cpu_cycle();
         }
-        None
     }
+}
 
+impl Read<u16, u8> for Bus {
+    fn read(&mut self, address: u16) -> Option<u8>{
+        let open_bus = self.open_bus;
+        let data = self.device_mut(address)
+            .and_then(|(device, address)| {
+                let data = device.read(address)?;
+                Some(drive(data, device.driven_bits(address), open_bus))
+            })
+            .unwrap_or(open_bus);
+        self.open_bus = data;
+        Some(data)
+    }
+
+    /// Always `Some`; see `Bus::peek`.
     fn read_only(&self, address: u16) -> Option<u8>{
-        if address >= 0x0000 && address <= 0xFFFF {
-            return Some(self.ram.borrow()[address as usize]);
+        Some(self.peek(address))
+    }
+}
+
+/// The value on the data bus when a device drives only the bits set in `driven`.
+fn drive(data: u8, driven: u8, open_bus: u8) -> u8 {
+    (data & driven) | (open_bus & !driven)
+}
+
+impl Write<u16, u8> for Bus {
+    fn write(&mut self, address: u16, data: u8) {
+        self.open_bus = data;
+        if let Some((device, address)) = self.device_mut(address) {
+            device.write(address, data);
         }
-        None
     }
 }
 
-impl Write<u16, u8> for Bus<'_>  {
-    fn write(&self, address: u16, data: u8) {
-        if address >= 0x0000 && address <= 0xFFFF {
-            self.ram.borrow_mut()[address as usize] = data;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shifts out one bit per read, the way a controller does, and drives only the low five
+    /// data lines.
+    #[derive(Debug, Clone)]
+    struct ShiftRegister {
+        bits: u8
+    }
+
+    impl Device for ShiftRegister {
+        fn driven_bits(&self, _address: u16) -> u8 {
+            0x1F
         }
     }
-}
\ No newline at end of file
+
+    impl Read<u16, u8> for ShiftRegister {
+        fn read(&mut self, _address: u16) -> Option<u8> {
+            let bit = self.bits & 1;
+            self.bits >>= 1;
+            Some(bit)
+        }
+
+        fn read_only(&self, _address: u16) -> Option<u8> {
+            Some(self.bits & 1)
+        }
+    }
+
+    impl Write<u16, u8> for ShiftRegister {
+        fn write(&mut self, _address: u16, _data: u8) {}
+    }
+
+    #[test]
+    fn peek_of_an_undriven_address_shows_the_latch() {
+        let mut bus = Bus::new();
+        bus.write(0x0000, 0x5A);
+        assert_eq!(bus.peek(0x5000), 0x5A);
+        assert_eq!(bus.peek(0x0800), 0x5A);
+        bus.write(0x0001, 0x33);
+        assert_eq!(bus.read_only(0x8000), Some(0x33));
+        assert_eq!(bus.open_bus(), 0x33);
+    }
+
+    #[test]
+    fn peek_has_no_side_effects_on_the_device_or_the_latch() {
+        let mut bus = Bus::new();
+        bus.map(0x4016..=0x4016, 0xFFFF, Box::new(ShiftRegister { bits: 0b10 }));
+        bus.write(0x0000, 0x5A);
+        assert_eq!(bus.peek(0x4016), 0x40);
+        assert_eq!(bus.peek(0x4016), 0x40);
+        assert_eq!(bus.open_bus(), 0x5A);
+        assert_eq!(bus.read(0x4016), Some(0x40));
+        assert_eq!(bus.read(0x4016), Some(0x41));
+        assert_eq!(bus.open_bus(), 0x41);
+    }
+}
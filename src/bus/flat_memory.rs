@@ -0,0 +1,52 @@
+use crate::traits::cpu_bus::CpuBus;
+use crate::traits::device::Device;
+use crate::traits::read::Read;
+use crate::traits::write::Write;
+
+/// 64KB of RAM filling the whole address space, for running the CPU outside the NES, e.g. on
+/// test programs written for a bare 6502 system. No device drives the interrupt lines.
+#[derive(Debug, Clone)]
+pub struct FlatMemory {
+    memory: Box<[u8]>
+}
+
+impl FlatMemory {
+    pub fn new() -> Self {
+        FlatMemory {
+            memory: vec![0; 0x10000].into_boxed_slice()
+        }
+    }
+
+    /// Copies `data` into memory starting at `address`, wrapping around at $FFFF.
+    pub fn load(&mut self, address: u16, data: &[u8]) {
+        for (offset, byte) in data.iter().enumerate() {
+            self.memory[address.wrapping_add(offset as u16) as usize] = *byte;
+        }
+    }
+}
+
+impl Default for FlatMemory {
+    fn default() -> Self {
+        FlatMemory::new()
+    }
+}
+
+impl CpuBus for FlatMemory {}
+
+impl Device for FlatMemory {}
+
+impl Read<u16, u8> for FlatMemory {
+    fn read(&mut self, address: u16) -> Option<u8>{
+        Some(self.memory[address as usize])
+    }
+
+    fn read_only(&self, address: u16) -> Option<u8>{
+        Some(self.memory[address as usize])
+    }
+}
+
+impl Write<u16, u8> for FlatMemory {
+    fn write(&mut self, address: u16, data: u8) {
+        self.memory[address as usize] = data;
+    }
+}
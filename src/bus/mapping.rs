@@ -0,0 +1,40 @@
+use crate::traits::device::Device;
+use std::ops::RangeInclusive;
+
+/// Identifies a device mapped into the bus, so it can be unmapped again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeviceHandle(usize);
+
+/// A device decoding the addresses in `start..=end`. The device sees `address & mask`, which is
+/// how partially decoded hardware mirrors itself: the NES PPU is mapped at $2000-$3FFF with mask
+/// $2007, so every eighth address reaches the same register.
+#[derive(Debug, Clone)]
+pub(crate) struct Mapping {
+    pub(crate) handle: DeviceHandle,
+    start: u16,
+    end: u16,
+    mask: u16,
+    pub(crate) device: Box<dyn Device>
+}
+
+impl Mapping {
+    pub(crate) fn new(handle: usize, range: RangeInclusive<u16>, mask: u16, device: Box<dyn Device>) -> Self {
+        assert!(range.start() <= range.end(), "empty address range {:04X?}", range);
+        Mapping {
+            handle: DeviceHandle(handle),
+            start: *range.start(),
+            end: *range.end(),
+            mask,
+            device
+        }
+    }
+
+    /// The address the device sees for a bus access to `address`, if it decodes it.
+    pub(crate) fn decode(&self, address: u16) -> Option<u16> {
+        if (self.start..=self.end).contains(&address) {
+            Some(address & self.mask)
+        } else {
+            None
+        }
+    }
+}
@@ -1 +1,4 @@
-pub mod bus;
\ No newline at end of file
+pub mod bus;
+pub mod flat_memory;
+pub mod mapping;
+pub mod ram;
\ No newline at end of file
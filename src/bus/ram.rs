@@ -0,0 +1,42 @@
+use crate::traits::device::Device;
+use crate::traits::read::Read;
+use crate::traits::write::Write;
+
+/// A block of RAM. Addresses wrap around its size, so it mirrors itself through whatever range
+/// it is mapped into.
+#[derive(Debug, Clone)]
+pub struct Ram {
+    memory: Box<[u8]>
+}
+
+impl Ram {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "RAM size must not be zero");
+        Ram {
+            memory: vec![0; size].into_boxed_slice()
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.memory.len()
+    }
+}
+
+impl Device for Ram {}
+
+impl Read<u16, u8> for Ram {
+    fn read(&mut self, address: u16) -> Option<u8>{
+        self.read_only(address)
+    }
+
+    fn read_only(&self, address: u16) -> Option<u8>{
+        Some(self.memory[address as usize % self.memory.len()])
+    }
+}
+
+impl Write<u16, u8> for Ram {
+    fn write(&mut self, address: u16, data: u8) {
+        let size = self.memory.len();
+        self.memory[address as usize % size] = data;
+    }
+}
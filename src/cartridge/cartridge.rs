@@ -0,0 +1,188 @@
+use crate::cartridge::cartridge_error::CartridgeError;
+use crate::cartridge::cartridge_memory::CartridgeMemory;
+use crate::cartridge::header::{Header, CHR_RAM_SIZE, HEADER_SIZE, PRG_RAM_BANK_SIZE, TRAINER_SIZE};
+use crate::cartridge::mirroring::Mirroring;
+use crate::mapper::axrom::Axrom;
+use crate::mapper::cnrom::Cnrom;
+use crate::mapper::mmc1::Mmc1;
+use crate::mapper::mmc3::Mmc3;
+use crate::mapper::nrom::Nrom;
+use crate::mapper::uxrom::Uxrom;
+use crate::traits::device::Device;
+use crate::traits::mapper::Mapper;
+use crate::traits::read::Read;
+use crate::traits::write::Write;
+use std::fs;
+use std::path::Path;
+
+/// Where the trainer is loaded, relative to the start of PRG-RAM at $6000.
+const TRAINER_OFFSET: usize = 0x1000;
+
+/// A game cartridge loaded from an iNES or NES 2.0 file. It sits on two buses: the CPU sees
+/// $4020-$FFFF through `Read` and `Write`, and the PPU sees the pattern tables at $0000-$1FFF
+/// through the `ppu_` methods. What each address reaches is up to the board's mapper.
+#[derive(Debug, Clone)]
+pub struct Cartridge {
+    header: Header,
+    trainer: Option<Vec<u8>>,
+    memory: CartridgeMemory,
+    mapper: Box<dyn Mapper>
+}
+
+impl Cartridge {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Cartridge, CartridgeError> {
+        Cartridge::from_bytes(&fs::read(path)?)
+    }
+
+    /// Parses an iNES or NES 2.0 image. Bytes past the ROMs the header declares, such as the
+    /// PlayChoice-10 hint screen, are ignored.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Cartridge, CartridgeError> {
+        let header = Header::parse(bytes)?;
+        if header.prg_rom_size == 0 {
+            return Err(CartridgeError::NoProgramRom);
+        }
+        let mapper: Box<dyn Mapper> = match header.mapper {
+            0 => Box::new(Nrom::new(&header)),
+            1 => Box::new(Mmc1::new(&header)),
+            2 => Box::new(Uxrom::new(&header)),
+            3 => Box::new(Cnrom::new(&header)),
+            4 => Box::new(Mmc3::new(&header)),
+            7 => Box::new(Axrom::new(&header)),
+            mapper => return Err(CartridgeError::UnsupportedMapper { mapper }),
+        };
+        if bytes.len() < header.file_size() {
+            return Err(CartridgeError::Truncated { expected: header.file_size(), actual: bytes.len() });
+        }
+
+        let mut offset = HEADER_SIZE;
+        let mut take = |size: usize| {
+            let block = bytes[offset..offset + size].to_vec();
+            offset += size;
+            block
+        };
+        let trainer = header.trainer.then(|| take(TRAINER_SIZE));
+        let prg_rom = take(header.prg_rom_size);
+        let chr = if header.chr_rom_size == 0 {
+            // A header that declares neither CHR-ROM nor CHR-RAM is taken to mean the usual 8KB.
+            let chr_ram_size = header.chr_ram_size + header.chr_nvram_size;
+            vec![0; if chr_ram_size == 0 { CHR_RAM_SIZE } else { chr_ram_size }]
+        } else {
+            take(header.chr_rom_size)
+        };
+        // Boards with a trainer always had the 8KB of PRG-RAM it is loaded into.
+        let mut prg_ram_size = header.prg_ram_size + header.prg_nvram_size;
+        if header.trainer {
+            prg_ram_size = prg_ram_size.max(PRG_RAM_BANK_SIZE);
+        }
+        let mut prg_ram = vec![0; prg_ram_size];
+        if let Some(trainer) = &trainer {
+            prg_ram[TRAINER_OFFSET..TRAINER_OFFSET + TRAINER_SIZE].copy_from_slice(trainer);
+        }
+
+        Ok(Cartridge {
+            header,
+            trainer,
+            memory: CartridgeMemory::new(prg_rom, prg_ram, chr, header.chr_rom_size == 0),
+            mapper
+        })
+    }
+
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    pub fn mapper(&self) -> u16 {
+        self.header.mapper
+    }
+
+    /// How the nametables are arranged at the moment, which the mapper may change.
+    pub fn mirroring(&self) -> Mirroring {
+        self.mapper.mirroring()
+    }
+
+    /// Whether the mapper is asserting the CPU's /IRQ line.
+    pub fn irq_line(&self) -> bool {
+        self.mapper.irq_line()
+    }
+
+    /// Tells the mapper a scanline has been rendered.
+    pub fn notify_scanline(&mut self) {
+        self.mapper.notify_scanline();
+    }
+
+    /// Tells the mapper a CPU cycle has passed.
+    pub fn notify_cpu_cycle(&mut self) {
+        self.mapper.notify_cpu_cycle();
+    }
+
+    /// Tells the mapper the PPU has put `address` on its bus without reaching the pattern
+    /// tables, as for nametable fetches.
+    pub fn notify_ppu_address(&mut self, address: u16) {
+        self.mapper.notify_ppu_address(address);
+    }
+
+    /// Whether the PRG-RAM is battery-backed, so `prg_ram` should be saved when the game is
+    /// closed and restored with `prg_ram_mut` when it is next loaded.
+    pub fn has_battery(&self) -> bool {
+        self.header.battery
+    }
+
+    pub fn trainer(&self) -> Option<&[u8]> {
+        self.trainer.as_deref()
+    }
+
+    pub fn prg_rom(&self) -> &[u8] {
+        self.memory.prg_rom()
+    }
+
+    pub fn prg_ram(&self) -> &[u8] {
+        self.memory.prg_ram()
+    }
+
+    pub fn prg_ram_mut(&mut self) -> &mut [u8] {
+        self.memory.prg_ram_mut()
+    }
+
+    /// The CHR-ROM, or `None` if the board has CHR-RAM instead.
+    pub fn chr_rom(&self) -> Option<&[u8]> {
+        (!self.memory.chr_is_ram()).then_some(self.memory.chr())
+    }
+
+    /// The CHR-RAM, or `None` if the board has CHR-ROM instead.
+    pub fn chr_ram(&self) -> Option<&[u8]> {
+        self.memory.chr_is_ram().then_some(self.memory.chr())
+    }
+
+    /// A PPU read from the pattern tables at $0000-$1FFF.
+    pub fn ppu_read(&mut self, address: u16) -> u8 {
+        self.mapper.ppu_read(&self.memory, address & 0x1FFF)
+    }
+
+    /// A PPU read from the pattern tables, without side effects.
+    pub fn ppu_read_only(&self, address: u16) -> u8 {
+        self.mapper.ppu_read_only(&self.memory, address & 0x1FFF)
+    }
+
+    /// A PPU write to the pattern tables, which only has an effect on CHR-RAM.
+    pub fn ppu_write(&mut self, address: u16, data: u8) {
+        self.mapper.ppu_write(&mut self.memory, address & 0x1FFF, data);
+    }
+}
+
+impl Device for Cartridge {}
+
+impl Read<u16, u8> for Cartridge {
+    fn read(&mut self, address: u16) -> Option<u8>{
+        self.mapper.cpu_read(&self.memory, address)
+    }
+
+    fn read_only(&self, address: u16) -> Option<u8>{
+        self.mapper.cpu_read_only(&self.memory, address)
+    }
+}
+
+impl Write<u16, u8> for Cartridge {
+    fn write(&mut self, address: u16, data: u8) {
+        self.mapper.cpu_write(&mut self.memory, address, data);
+    }
+}
@@ -0,0 +1,47 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Why a ROM image could not be loaded.
+#[derive(Debug)]
+pub enum CartridgeError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The file does not start with the `NES<EOF>` signature.
+    InvalidSignature,
+    /// The file is shorter than its header says it should be.
+    Truncated { expected: usize, actual: usize },
+    /// The header declares no PRG-ROM, so there is no program to run.
+    NoProgramRom,
+    /// The board uses a mapper the emulator does not implement.
+    UnsupportedMapper { mapper: u16 },
+}
+
+impl fmt::Display for CartridgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CartridgeError::Io(error) => write!(f, "could not read ROM: {}", error),
+            CartridgeError::InvalidSignature => write!(f, "not an iNES file"),
+            CartridgeError::Truncated { expected, actual } => {
+                write!(f, "ROM is truncated: expected {} bytes, found {}", expected, actual)
+            },
+            CartridgeError::NoProgramRom => write!(f, "ROM has no PRG-ROM"),
+            CartridgeError::UnsupportedMapper { mapper } => write!(f, "mapper {} is not supported", mapper),
+        }
+    }
+}
+
+impl Error for CartridgeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CartridgeError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CartridgeError {
+    fn from(error: io::Error) -> Self {
+        CartridgeError::Io(error)
+    }
+}
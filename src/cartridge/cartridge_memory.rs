@@ -0,0 +1,105 @@
+/// The ROM and RAM chips on a cartridge board. Mappers decide which bank of each the CPU and
+/// PPU see, and read and write them through the methods here, which wrap bank numbers larger
+/// than the chip around as the unconnected upper address lines would.
+#[derive(Debug, Clone)]
+pub struct CartridgeMemory {
+    prg_rom: Vec<u8>,
+    prg_ram: Vec<u8>,
+    chr: Vec<u8>,
+    chr_is_ram: bool
+}
+
+impl CartridgeMemory {
+    pub fn new(prg_rom: Vec<u8>, prg_ram: Vec<u8>, chr: Vec<u8>, chr_is_ram: bool) -> Self {
+        assert!(!prg_rom.is_empty() && !chr.is_empty(), "cartridge needs PRG-ROM and CHR memory");
+        CartridgeMemory {
+            prg_rom,
+            prg_ram,
+            chr,
+            chr_is_ram
+        }
+    }
+
+    pub fn prg_rom(&self) -> &[u8] {
+        &self.prg_rom
+    }
+
+    pub fn prg_ram(&self) -> &[u8] {
+        &self.prg_ram
+    }
+
+    pub fn prg_ram_mut(&mut self) -> &mut [u8] {
+        &mut self.prg_ram
+    }
+
+    pub fn chr(&self) -> &[u8] {
+        &self.chr
+    }
+
+    pub fn chr_is_ram(&self) -> bool {
+        self.chr_is_ram
+    }
+
+    /// Number of `bank_size` banks of PRG-ROM, at least one.
+    pub fn prg_rom_banks(&self, bank_size: usize) -> usize {
+        (self.prg_rom.len() / bank_size).max(1)
+    }
+
+    /// Number of `bank_size` banks of CHR memory, at least one.
+    pub fn chr_banks(&self, bank_size: usize) -> usize {
+        (self.chr.len() / bank_size).max(1)
+    }
+
+    /// The byte at `address` within PRG-ROM bank `bank`, where banks are `bank_size` bytes.
+    pub fn read_prg_rom(&self, bank: usize, bank_size: usize, address: u16) -> u8 {
+        self.prg_rom[offset(bank, bank_size, address, self.prg_rom.len())]
+    }
+
+    /// The byte at `address` within PRG-RAM, or `None` if the board has none.
+    pub fn read_prg_ram(&self, address: u16) -> Option<u8> {
+        if self.prg_ram.is_empty() {
+            return None;
+        }
+        Some(self.prg_ram[address as usize % self.prg_ram.len()])
+    }
+
+    pub fn write_prg_ram(&mut self, address: u16, data: u8) {
+        if !self.prg_ram.is_empty() {
+            let size = self.prg_ram.len();
+            self.prg_ram[address as usize % size] = data;
+        }
+    }
+
+    /// The byte at `address` within CHR bank `bank`, where banks are `bank_size` bytes.
+    pub fn read_chr(&self, bank: usize, bank_size: usize, address: u16) -> u8 {
+        self.chr[offset(bank, bank_size, address, self.chr.len())]
+    }
+
+    /// Writes CHR-RAM. Writes to CHR-ROM are ignored.
+    pub fn write_chr(&mut self, bank: usize, bank_size: usize, address: u16, data: u8) {
+        if self.chr_is_ram {
+            let offset = offset(bank, bank_size, address, self.chr.len());
+            self.chr[offset] = data;
+        }
+    }
+}
+
+fn offset(bank: usize, bank_size: usize, address: u16, size: usize) -> usize {
+    let banks = (size / bank_size).max(1);
+    ((bank % banks) * bank_size + address as usize % bank_size) % size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bank_numbers_wrap_to_the_chip_size() {
+        let prg_rom = (0..4).flat_map(|bank| vec![bank; 0x4000]).collect();
+        let chr = (0..2).flat_map(|bank| vec![bank; 0x1000]).collect();
+        let memory = CartridgeMemory::new(prg_rom, Vec::new(), chr, false);
+        assert_eq!(memory.read_prg_rom(6, 0x4000, 0x0000), 2);
+        assert_eq!(memory.read_prg_rom(11, 0x4000, 0x3FFF), 3);
+        assert_eq!(memory.read_chr(3, 0x1000, 0x0FFF), 1);
+    }
+}
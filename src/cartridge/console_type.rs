@@ -0,0 +1,17 @@
+/// The machine a ROM was dumped from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConsoleType {
+    /// A Nintendo Entertainment System or Famicom.
+    #[default]
+    Nes,
+    /// A Vs. System arcade board. `ppu` names the RGB PPU fitted, which decides the palette
+    /// (0 is the RP2C03B), and `hardware` whether the game needs the Vs. Dual System or
+    /// copy-protection hardware (0 is a plain Vs. Unisystem). Both are 0 in iNES headers,
+    /// which do not record them.
+    VsSystem { ppu: u8, hardware: u8 },
+    /// A PlayChoice-10 arcade board.
+    Playchoice10,
+    /// One of the NES 2.0 extended console types, such as a Famiclone with decimal mode or the
+    /// VT01 family, by its number in byte 13.
+    Extended(u8),
+}
@@ -0,0 +1,299 @@
+use crate::cartridge::cartridge_error::CartridgeError;
+use crate::cartridge::console_type::ConsoleType;
+use crate::cartridge::header_format::HeaderFormat;
+use crate::cartridge::mirroring::Mirroring;
+use crate::cartridge::timing::Timing;
+
+pub const HEADER_SIZE: usize = 16;
+pub const TRAINER_SIZE: usize = 512;
+pub const PRG_ROM_BANK_SIZE: usize = 0x4000;
+pub const CHR_ROM_BANK_SIZE: usize = 0x2000;
+pub const PRG_RAM_BANK_SIZE: usize = 0x2000;
+pub const CHR_RAM_SIZE: usize = 0x2000;
+
+const SIGNATURE: [u8; 4] = *b"NES\x1A";
+const DISK_DUDE: &[u8] = b"DiskDude!";
+
+/// The 16-byte header at the start of an iNES or NES 2.0 file, describing the board the ROMs
+/// came from. Fields that older formats do not record are filled in with what the boards of the
+/// time almost always had.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub format: HeaderFormat,
+    /// Size of the PRG-ROM in bytes.
+    pub prg_rom_size: usize,
+    /// Size of the CHR-ROM in bytes. Zero means the board has CHR-RAM.
+    pub chr_rom_size: usize,
+    /// Size of the volatile PRG-RAM at $6000-$7FFF in bytes.
+    pub prg_ram_size: usize,
+    /// Size of the battery-backed PRG-RAM in bytes.
+    pub prg_nvram_size: usize,
+    /// Size of the volatile CHR-RAM in bytes.
+    pub chr_ram_size: usize,
+    /// Size of the battery-backed CHR-RAM in bytes.
+    pub chr_nvram_size: usize,
+    pub mapper: u16,
+    /// Distinguishes boards that share a mapper number but behave differently. Always 0
+    /// outside NES 2.0.
+    pub submapper: u8,
+    pub mirroring: Mirroring,
+    /// The board has battery-backed memory that should be saved between sessions.
+    pub battery: bool,
+    /// A 512-byte trainer sits between the header and the PRG-ROM, to be loaded at $7000.
+    pub trainer: bool,
+    pub timing: Timing,
+    pub console_type: ConsoleType,
+    /// Number of ROMs after the CHR-ROM that are not part of the cartridge, such as the
+    /// PlayChoice-10 hint screen.
+    pub misc_roms: u8,
+    /// The input device the game expects to be plugged in, by its NES 2.0 number; 0 when not
+    /// specified and 1 for standard controllers.
+    pub default_expansion_device: u8,
+}
+
+impl Header {
+    pub fn parse(bytes: &[u8]) -> Result<Header, CartridgeError> {
+        if bytes.len() < HEADER_SIZE {
+            if bytes.len() >= SIGNATURE.len() && bytes[..SIGNATURE.len()] == SIGNATURE {
+                return Err(CartridgeError::Truncated { expected: HEADER_SIZE, actual: bytes.len() });
+            }
+            return Err(CartridgeError::InvalidSignature);
+        }
+        if bytes[..SIGNATURE.len()] != SIGNATURE {
+            return Err(CartridgeError::InvalidSignature);
+        }
+        Ok(match detect_format(bytes) {
+            HeaderFormat::Nes20 => parse_nes_20(bytes),
+            format => parse_ines(bytes, format),
+        })
+    }
+
+    /// Number of bytes the file must hold for the ROMs the header declares.
+    pub fn file_size(&self) -> usize {
+        let trainer = if self.trainer { TRAINER_SIZE } else { 0 };
+        (HEADER_SIZE + trainer)
+            .saturating_add(self.prg_rom_size)
+            .saturating_add(self.chr_rom_size)
+    }
+}
+
+/// Follows the detection procedure recommended on the NESdev wiki. A header claiming to be
+/// NES 2.0 is only believed if the file is large enough for the ROM sizes it declares that way.
+fn detect_format(bytes: &[u8]) -> HeaderFormat {
+    let trainer = if bytes[6] & 0x04 != 0 { TRAINER_SIZE } else { 0 };
+    match bytes[7] & 0x0C {
+        0x08 => {
+            let size = (HEADER_SIZE + trainer)
+                .saturating_add(rom_size(bytes[4], bytes[9] & 0x0F, PRG_ROM_BANK_SIZE))
+                .saturating_add(rom_size(bytes[5], bytes[9] >> 4, CHR_ROM_BANK_SIZE));
+            if size <= bytes.len() {
+                HeaderFormat::Nes20
+            } else {
+                HeaderFormat::ArchaicInes
+            }
+        },
+        _ if bytes[7..].starts_with(DISK_DUDE) => HeaderFormat::DiskDude,
+        0x00 if bytes[12..HEADER_SIZE].iter().all(|&byte| byte == 0) => HeaderFormat::Ines,
+        _ => HeaderFormat::ArchaicInes,
+    }
+}
+
+fn parse_ines(bytes: &[u8], format: HeaderFormat) -> Header {
+    let flags_6 = bytes[6];
+    let chr_rom_size = bytes[5] as usize * CHR_ROM_BANK_SIZE;
+    let battery = flags_6 & 0x02 != 0;
+    let mut header = Header {
+        format,
+        prg_rom_size: bytes[4] as usize * PRG_ROM_BANK_SIZE,
+        chr_rom_size,
+        prg_ram_size: 0,
+        prg_nvram_size: 0,
+        chr_ram_size: if chr_rom_size == 0 { CHR_RAM_SIZE } else { 0 },
+        chr_nvram_size: 0,
+        mapper: (flags_6 >> 4) as u16,
+        submapper: 0,
+        mirroring: mirroring(flags_6),
+        battery,
+        trainer: flags_6 & 0x04 != 0,
+        timing: Timing::Ntsc,
+        console_type: ConsoleType::Nes,
+        misc_roms: 0,
+        default_expansion_device: 0,
+    };
+    // Byte 8 was added late to the format and is zero in most files; the boards those files
+    // come from have 8KB of PRG-RAM, or none that anything relies on being absent.
+    let mut prg_ram_banks = 1;
+    if format == HeaderFormat::Ines {
+        let flags_7 = bytes[7];
+        header.mapper |= (flags_7 & 0xF0) as u16;
+        header.console_type = if flags_7 & 0x01 != 0 {
+            ConsoleType::VsSystem { ppu: 0, hardware: 0 }
+        } else if flags_7 & 0x02 != 0 {
+            ConsoleType::Playchoice10
+        } else {
+            ConsoleType::Nes
+        };
+        prg_ram_banks = bytes[8].max(1) as usize;
+        if bytes[9] & 0x01 != 0 {
+            header.timing = Timing::Pal;
+        }
+    }
+    if battery {
+        header.prg_nvram_size = prg_ram_banks * PRG_RAM_BANK_SIZE;
+    } else {
+        header.prg_ram_size = prg_ram_banks * PRG_RAM_BANK_SIZE;
+    }
+    header
+}
+
+fn parse_nes_20(bytes: &[u8]) -> Header {
+    let flags_6 = bytes[6];
+    let flags_7 = bytes[7];
+    let console_type = match flags_7 & 0x03 {
+        0 => ConsoleType::Nes,
+        1 => ConsoleType::VsSystem { ppu: bytes[13] & 0x0F, hardware: bytes[13] >> 4 },
+        2 => ConsoleType::Playchoice10,
+        _ => ConsoleType::Extended(bytes[13] & 0x0F),
+    };
+    let timing = match bytes[12] & 0x03 {
+        0 => Timing::Ntsc,
+        1 => Timing::Pal,
+        2 => Timing::MultipleRegion,
+        _ => Timing::Dendy,
+    };
+
+    Header {
+        format: HeaderFormat::Nes20,
+        prg_rom_size: rom_size(bytes[4], bytes[9] & 0x0F, PRG_ROM_BANK_SIZE),
+        chr_rom_size: rom_size(bytes[5], bytes[9] >> 4, CHR_ROM_BANK_SIZE),
+        prg_ram_size: ram_size(bytes[10] & 0x0F),
+        prg_nvram_size: ram_size(bytes[10] >> 4),
+        chr_ram_size: ram_size(bytes[11] & 0x0F),
+        chr_nvram_size: ram_size(bytes[11] >> 4),
+        mapper: (flags_6 >> 4) as u16 | (flags_7 & 0xF0) as u16 | ((bytes[8] & 0x0F) as u16) << 8,
+        submapper: bytes[8] >> 4,
+        mirroring: mirroring(flags_6),
+        battery: flags_6 & 0x02 != 0,
+        trainer: flags_6 & 0x04 != 0,
+        timing,
+        console_type,
+        misc_roms: bytes[14] & 0x03,
+        default_expansion_device: bytes[15] & 0x3F,
+    }
+}
+
+fn mirroring(flags_6: u8) -> Mirroring {
+    if flags_6 & 0x08 != 0 {
+        Mirroring::FourScreen
+    } else if flags_6 & 0x01 != 0 {
+        Mirroring::Vertical
+    } else {
+        Mirroring::Horizontal
+    }
+}
+
+/// A NES 2.0 ROM size. With the top nibble of $F the low byte is in exponent-multiplier form,
+/// `EEEEEEMM` for 2^E * (MM * 2 + 1) bytes, which can describe sizes that are not a whole
+/// number of banks; otherwise the size is a 12-bit count of `bank_size` banks.
+fn rom_size(low: u8, high: u8, bank_size: usize) -> usize {
+    if high == 0x0F {
+        let multiplier = (low & 0x03) as usize * 2 + 1;
+        1usize.checked_shl((low >> 2) as u32)
+            .map_or(usize::MAX, |size| size.saturating_mul(multiplier))
+    } else {
+        (((high as usize) << 8) | low as usize) * bank_size
+    }
+}
+
+/// A NES 2.0 RAM size, stored as a shift count: 64 << shift bytes, or none for zero.
+fn ram_size(shift: u8) -> usize {
+    if shift == 0 {
+        0
+    } else {
+        64 << shift
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A header with the given bytes 4-15, followed by `payload` bytes of ROM.
+    fn image(fields: [u8; 12], payload: usize) -> Vec<u8> {
+        let mut bytes = SIGNATURE.to_vec();
+        bytes.extend_from_slice(&fields);
+        bytes.resize(HEADER_SIZE + payload, 0);
+        bytes
+    }
+
+    #[test]
+    fn detects_ines() {
+        let header = Header::parse(&image([2, 1, 0x11, 0x40, 2, 1, 0, 0, 0, 0, 0, 0], 0)).unwrap();
+        assert_eq!(header.format, HeaderFormat::Ines);
+        assert_eq!(header.mapper, 0x41);
+        assert_eq!(header.mirroring, Mirroring::Vertical);
+        assert_eq!(header.prg_ram_size, 2 * PRG_RAM_BANK_SIZE);
+        assert_eq!(header.timing, Timing::Pal);
+    }
+
+    #[test]
+    fn detects_nes_20() {
+        let bytes = image([2, 1, 0x42, 0x08, 0x31, 0x00, 0x70, 0x07, 0x01, 0x00, 0x00, 0x01], 0xA000);
+        let header = Header::parse(&bytes).unwrap();
+        assert_eq!(header.format, HeaderFormat::Nes20);
+        assert_eq!(header.mapper, 0x104);
+        assert_eq!(header.submapper, 3);
+        assert_eq!(header.timing, Timing::Pal);
+        assert_eq!(header.default_expansion_device, 1);
+    }
+
+    #[test]
+    fn garbage_in_the_padding_is_archaic() {
+        let header = Header::parse(&image([1, 1, 0x10, 0x40, 0, 0, 0, 0, 0, 0x42, 0, 0], 0)).unwrap();
+        assert_eq!(header.format, HeaderFormat::ArchaicInes);
+        // Byte 7 is not trusted, so only the low nibble of the mapper number is kept.
+        assert_eq!(header.mapper, 1);
+        assert_eq!(header.prg_ram_size, PRG_RAM_BANK_SIZE);
+    }
+
+    #[test]
+    fn detects_disk_dude() {
+        let mut fields = [1, 1, 0x20, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        fields[3..12].copy_from_slice(DISK_DUDE);
+        let header = Header::parse(&image(fields, 0)).unwrap();
+        assert_eq!(header.format, HeaderFormat::DiskDude);
+        assert_eq!(header.mapper, 2);
+    }
+
+    #[test]
+    fn nes_20_sizes_larger_than_the_file_fall_back_to_archaic() {
+        let bytes = image([2, 1, 0x00, 0x08, 0, 0, 0, 0, 0, 0, 0, 0], 0x4000);
+        let header = Header::parse(&bytes).unwrap();
+        assert_eq!(header.format, HeaderFormat::ArchaicInes);
+        assert_eq!(header.prg_rom_size, 2 * PRG_ROM_BANK_SIZE);
+    }
+
+    #[test]
+    fn exponent_multiplier_rom_size() {
+        assert_eq!(rom_size(14 << 2, 0x0F, PRG_ROM_BANK_SIZE), 0x4000);
+        assert_eq!(rom_size((14 << 2) | 0x01, 0x0F, PRG_ROM_BANK_SIZE), 3 * 0x4000);
+        assert_eq!(rom_size((10 << 2) | 0x03, 0x0F, CHR_ROM_BANK_SIZE), 7 * 0x400);
+        assert_eq!(rom_size(0xFF, 0x0F, PRG_ROM_BANK_SIZE), usize::MAX);
+        assert_eq!(rom_size(0x02, 0x01, PRG_ROM_BANK_SIZE), 0x102 * PRG_ROM_BANK_SIZE);
+    }
+
+    #[test]
+    fn ram_shift_sizes() {
+        assert_eq!(ram_size(0), 0);
+        assert_eq!(ram_size(1), 128);
+        assert_eq!(ram_size(7), 0x2000);
+        assert_eq!(ram_size(9), 0x8000);
+
+        let bytes = image([1, 0, 0x00, 0x08, 0, 0, 0x97, 0x07, 0, 0, 0, 0], 0x4000);
+        let header = Header::parse(&bytes).unwrap();
+        assert_eq!(header.prg_ram_size, 0x2000);
+        assert_eq!(header.prg_nvram_size, 0x8000);
+        assert_eq!(header.chr_ram_size, 0x2000);
+        assert_eq!(header.chr_nvram_size, 0);
+    }
+}
@@ -0,0 +1,15 @@
+/// Which revision of the iNES header a file uses, which decides how far its fields can be
+/// trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderFormat {
+    /// The original iNES format, with bytes 12-15 clear.
+    Ines,
+    /// NES 2.0, identified by bits 2-3 of byte 7 being `10`.
+    Nes20,
+    /// An iNES header from before bytes 7-15 were defined, which may hold anything. Only the
+    /// low nibble of the mapper number, in byte 6, is used.
+    ArchaicInes,
+    /// An archaic header with the signature of an old ROM dumping tool, "DiskDude!", written
+    /// over bytes 7-15. Treated like `ArchaicInes`.
+    DiskDude,
+}
@@ -0,0 +1,17 @@
+/// How the cartridge wires the PPU's two kilobytes of nametable RAM into the four nametables at
+/// $2000-$2FFF.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mirroring {
+    /// $2000 and $2400 share one nametable, $2800 and $2C00 the other. Used by vertically
+    /// scrolling games.
+    Horizontal,
+    /// $2000 and $2800 share one nametable, $2400 and $2C00 the other. Used by horizontally
+    /// scrolling games.
+    Vertical,
+    /// The cartridge supplies another two kilobytes of RAM, so each nametable is distinct.
+    FourScreen,
+    /// All four nametables show the first kilobyte of nametable RAM.
+    SingleScreenLower,
+    /// All four nametables show the second kilobyte of nametable RAM.
+    SingleScreenUpper,
+}
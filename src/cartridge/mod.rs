@@ -0,0 +1,8 @@
+pub mod cartridge;
+pub mod cartridge_error;
+pub mod cartridge_memory;
+pub mod console_type;
+pub mod header;
+pub mod header_format;
+pub mod mirroring;
+pub mod timing;
\ No newline at end of file
@@ -0,0 +1,13 @@
+/// The CPU and PPU timing a game was made for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Timing {
+    /// RP2C02, as in North America and Japan.
+    #[default]
+    Ntsc,
+    /// RP2C07, as in Europe and Australia.
+    Pal,
+    /// The game adapts to whichever region it runs on.
+    MultipleRegion,
+    /// UA6538, as in the Dendy and other Famiclones.
+    Dendy,
+}
@@ -0,0 +1,125 @@
+use crate::bus::bus::Bus;
+use crate::cartridge::cartridge::Cartridge;
+use crate::cpu::cpu_6502::Cpu6502;
+use crate::cpu::emulation_error::EmulationError;
+use crate::cpu::step::Step;
+use crate::cpu::trace::trace_line;
+
+/// A whole NES: the CPU and the bus with everything mapped into it. The console owns both and
+/// lends the bus to the CPU for each cycle, so it can be moved between threads, and cloning it
+/// takes a snapshot that can be run forward independently of the original.
+#[derive(Debug, Clone, Default)]
+pub struct Console {
+    cpu: Cpu6502,
+    bus: Bus
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Console {
+            cpu: Cpu6502::new(),
+            bus: Bus::new()
+        }
+    }
+
+    pub fn cpu(&self) -> &Cpu6502 {
+        &self.cpu
+    }
+
+    pub fn cpu_mut(&mut self) -> &mut Cpu6502 {
+        &mut self.cpu
+    }
+
+    pub fn bus(&self) -> &Bus {
+        &self.bus
+    }
+
+    pub fn bus_mut(&mut self) -> &mut Bus {
+        &mut self.bus
+    }
+
+    /// Swaps in `cartridge` and resets, as if the console had been switched off and on again
+    /// with the new game. Returns the cartridge previously inserted, if any.
+    pub fn insert_cartridge(&mut self, cartridge: Cartridge) -> Option<Cartridge> {
+        let previous = self.bus.insert_cartridge(cartridge);
+        self.reset();
+        previous
+    }
+
+    /// The value at `address` as the CPU would read it now, without side effects on any device.
+    pub fn peek(&self, address: u16) -> u8 {
+        self.bus.peek(address)
+    }
+
+    /// The nestest-style trace line for the instruction about to run, built from peeks only.
+    pub fn trace_line(&self) -> String {
+        trace_line(&self.cpu, &self.bus)
+    }
+
+    /// Presses the reset button. The CPU runs its reset sequence over the next seven cycles.
+    pub fn reset(&mut self) {
+        self.cpu.reset();
+    }
+
+    /// Runs a single CPU cycle.
+    pub fn clock(&mut self) -> Result<(), EmulationError> {
+        self.cpu.clock(&mut self.bus)
+    }
+
+    /// Runs the CPU to the end of the instruction in progress, or of the next instruction or
+    /// interrupt sequence.
+    pub fn step_instruction(&mut self) -> Result<Step, EmulationError> {
+        self.cpu.step_instruction(&mut self.bus)
+    }
+
+    pub fn run_cycles(&mut self, cycles: u64) -> Result<(), EmulationError> {
+        self.cpu.run_cycles(&mut self.bus, cycles)
+    }
+
+    /// Steps whole instructions until `predicate` holds, checking it before each one. Returns
+    /// the number of cycles run.
+    pub fn run_until<F>(&mut self, mut predicate: F) -> Result<u64, EmulationError>
+        where F: FnMut(&Cpu6502, &Bus) -> bool {
+        let start = self.cpu.total_cycles();
+        while !predicate(&self.cpu, &self.bus) {
+            self.cpu.step_instruction(&mut self.bus)?;
+        }
+        Ok(self.cpu.total_cycles() - start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::read::Read;
+    use crate::traits::write::Write;
+
+    #[test]
+    fn console_can_be_sent_and_cloned() {
+        fn _assert<T: Send + Clone>() {}
+        _assert::<Console>();
+    }
+
+    #[test]
+    fn clone_is_a_snapshot_that_runs_independently() {
+        // INC $10; JMP $0200
+        let mut console = Console::new();
+        for (offset, byte) in [0xE6, 0x10, 0x4C, 0x00, 0x02].into_iter().enumerate() {
+            console.bus_mut().write(0x0200 + offset as u16, byte);
+        }
+        console.cpu_mut().set_program_counter(0x0200);
+        console.step_instruction().unwrap();
+        let mut snapshot = console.clone();
+
+        let cycles = console.run_until(|_, bus| bus.read_only(0x0010) == Some(3)).unwrap();
+        assert_eq!(cycles, 16);
+        assert_eq!(console.cpu().program_counter(), 0x0202);
+        assert_eq!(snapshot.bus().read_only(0x0010), Some(1));
+        assert_eq!(snapshot.cpu().program_counter(), 0x0202);
+
+        snapshot.step_instruction().unwrap();
+        snapshot.step_instruction().unwrap();
+        assert_eq!(snapshot.bus().read_only(0x0010), Some(2));
+        assert_eq!(console.bus().read_only(0x0010), Some(3));
+    }
+}
@@ -0,0 +1 @@
+pub mod console;
\ No newline at end of file
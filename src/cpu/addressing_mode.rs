@@ -1,6 +1,26 @@
 use crate::cpu::cpu_6502::Cpu6502;
+use crate::cpu::emulation_error::EmulationError;
+use crate::cpu::instruction::Instruction;
+use crate::cpu::opcode::MemoryAccess;
+use crate::traits::cpu_bus::CpuBus;
 
-#[derive(PartialEq, Eq, Clone)]
+/// Where an addressing mode has got to after running one cycle.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AddressingCycle {
+    /// More addressing cycles are needed.
+    Pending,
+    /// `address_absolute` holds the final effective address; the memory access starts on the
+    /// next cycle.
+    Ready,
+    /// The operand has been read into `fetched` (or is the accumulator), and the instruction
+    /// completes on this cycle.
+    Operand,
+    /// The operand has been read into `fetched` on this cycle, and a read-modify-write
+    /// continues from its second access on the next one.
+    Fetched,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum AddressingMode {
     Implied,
     Accumulator,
@@ -15,50 +35,225 @@ pub enum AddressingMode {
     Indirect,
     IndirectX,
     IndirectY,
+    /// `($nn)`, 65C02 only.
+    ZeroPageIndirect,
+    /// `($nnnn,X)`, used by the 65C02's JMP.
+    AbsoluteIndexedIndirect,
+    /// `$nn,$rrrr`, the zero page address and branch offset of BBR and BBS.
+    ZeroPageRelative,
 }
 
 impl AddressingMode {
-    pub fn execute(&self, cpu: &mut Cpu6502) -> u8{
+    /// Total size in bytes of an instruction using this mode, opcode included.
+    pub const fn instruction_length(&self) -> u8 {
         match self {
-            &AddressingMode::Implied => {
-                0
+            AddressingMode::Implied | AddressingMode::Accumulator => 1,
+            AddressingMode::Immediate | AddressingMode::ZeroPage | AddressingMode::ZeroPageX
+            | AddressingMode::ZeroPageY | AddressingMode::Relative | AddressingMode::IndirectX
+            | AddressingMode::IndirectY | AddressingMode::ZeroPageIndirect => 2,
+            AddressingMode::Absolute | AddressingMode::AbsoluteX | AddressingMode::AbsoluteY
+            | AddressingMode::Indirect | AddressingMode::AbsoluteIndexedIndirect
+            | AddressingMode::ZeroPageRelative => 3,
+        }
+    }
+
+    /// Runs cycle `step` of the addressing sequence, where step 1 is the cycle after the opcode
+    /// fetch. Every cycle performs exactly one bus access, including the dummy reads real
+    /// hardware makes while it adds an index register or fixes up a page crossing.
+    pub fn execute(&self, cpu: &mut Cpu6502, bus: &mut dyn CpuBus, step: u8, instruction: &Instruction) -> Result<AddressingCycle, EmulationError> {
+        Ok(match (self, step) {
+            (&AddressingMode::Implied, _) => {
+                cpu.poll_interrupts(bus);
+                cpu.read_byte(bus, cpu.program_counter)?;
+                AddressingCycle::Operand
+            },
+            (&AddressingMode::Accumulator, _) => {
+                cpu.poll_interrupts(bus);
+                cpu.read_byte(bus, cpu.program_counter)?;
+                cpu.fetched = cpu.accumulator;
+                AddressingCycle::Operand
+            },
+            (&AddressingMode::Immediate, _) => {
+                cpu.poll_interrupts(bus);
+                cpu.fetched = cpu.read_program_counter(bus)?;
+                AddressingCycle::Operand
+            },
+            (&AddressingMode::ZeroPage, _) => {
+                cpu.address_absolute = cpu.read_program_counter(bus)? as u16;
+                AddressingCycle::Ready
+            },
+            (&AddressingMode::ZeroPageX, 1) | (&AddressingMode::ZeroPageY, 1) => {
+                cpu.address_absolute = cpu.read_program_counter(bus)? as u16;
+                AddressingCycle::Pending
             },
-            &AddressingMode::Accumulator => {
-                0
+            (&AddressingMode::ZeroPageX, _) => {
+                cpu.dummy_read(bus, cpu.address_absolute)?;
+                cpu.address_absolute = (cpu.address_absolute as u8).wrapping_add(cpu.x_register) as u16;
+                AddressingCycle::Ready
             },
-            &AddressingMode::Immediate => {
-                0
+            (&AddressingMode::ZeroPageY, _) => {
+                cpu.dummy_read(bus, cpu.address_absolute)?;
+                cpu.address_absolute = (cpu.address_absolute as u8).wrapping_add(cpu.y_register) as u16;
+                AddressingCycle::Ready
             },
-            &AddressingMode::Absolute => {
-                0
+            (&AddressingMode::Absolute, 1) | (&AddressingMode::AbsoluteX, 1) | (&AddressingMode::AbsoluteY, 1) => {
+                cpu.address_absolute = cpu.read_program_counter(bus)? as u16;
+                AddressingCycle::Pending
             },
-            &AddressingMode::ZeroPage => {
-                0
+            (&AddressingMode::Absolute, _) => {
+                cpu.address_absolute |= (cpu.read_program_counter(bus)? as u16) << 8;
+                AddressingCycle::Ready
             },
-            &AddressingMode::ZeroPageX => {
-                0
+            (&AddressingMode::AbsoluteX, 2) => {
+                let high = cpu.read_program_counter(bus)?;
+                add_index(cpu, high, cpu.x_register);
+                AddressingCycle::Pending
             },
-            &AddressingMode::ZeroPageY => {
-                0
+            (&AddressingMode::AbsoluteY, 2) => {
+                let high = cpu.read_program_counter(bus)?;
+                add_index(cpu, high, cpu.y_register);
+                AddressingCycle::Pending
             },
-            &AddressingMode::AbsoluteX => {
-                0
+            (&AddressingMode::AbsoluteX, _) | (&AddressingMode::AbsoluteY, _) => {
+                read_indexed(cpu, bus, instruction)?
             },
-            &AddressingMode::AbsoluteY => {
-                0
+            (&AddressingMode::IndirectX, 1) | (&AddressingMode::IndirectY, 1)
+            | (&AddressingMode::ZeroPageIndirect, 1) => {
+                cpu.pointer = cpu.read_program_counter(bus)?;
+                AddressingCycle::Pending
             },
-            &AddressingMode::Relative => {
-                0
+            (&AddressingMode::IndirectX, 2) => {
+                cpu.dummy_read(bus, cpu.pointer as u16)?;
+                cpu.pointer = cpu.pointer.wrapping_add(cpu.x_register);
+                AddressingCycle::Pending
             },
-            &AddressingMode::Indirect => {
-                0
+            (&AddressingMode::IndirectX, 3) | (&AddressingMode::IndirectY, 2)
+            | (&AddressingMode::ZeroPageIndirect, 2) => {
+                cpu.address_absolute = cpu.read_byte(bus, cpu.pointer as u16)? as u16;
+                AddressingCycle::Pending
             },
-            &AddressingMode::IndirectX => {
-                0
+            (&AddressingMode::IndirectX, _) | (&AddressingMode::ZeroPageIndirect, _) => {
+                cpu.address_absolute |= (cpu.read_byte(bus, cpu.pointer.wrapping_add(1) as u16)? as u16) << 8;
+                AddressingCycle::Ready
             },
-            &AddressingMode::IndirectY => {
-                0
+            (&AddressingMode::IndirectY, 3) => {
+                let high = cpu.read_byte(bus, cpu.pointer.wrapping_add(1) as u16)?;
+                add_index(cpu, high, cpu.y_register);
+                AddressingCycle::Pending
             },
+            (&AddressingMode::IndirectY, _) => {
+                read_indexed(cpu, bus, instruction)?
+            },
+            (&AddressingMode::Relative, _) | (&AddressingMode::Indirect, _)
+            | (&AddressingMode::AbsoluteIndexedIndirect, _) | (&AddressingMode::ZeroPageRelative, _) => {
+                unreachable!("branches and indirect jumps are sequenced by the CPU")
+            },
+        })
+    }
+}
+
+/// Combines the high byte with the low byte already in `address_absolute` and adds the index.
+fn add_index(cpu: &mut Cpu6502, high: u8, index: u8) {
+    let base = ((high as u16) << 8) | cpu.address_absolute;
+    cpu.address_absolute = base.wrapping_add(index as u16);
+    cpu.page_crossed = base & 0xFF00 != cpu.address_absolute & 0xFF00;
+}
+
+/// The CPU reads from the indexed address before the carry into the high byte is applied. For
+/// an instruction with a page-cross penalty that does not cross a page this is the operand;
+/// otherwise it is a dummy read and the access is repeated at the corrected address.
+fn read_indexed(cpu: &mut Cpu6502, bus: &mut dyn CpuBus, instruction: &Instruction) -> Result<AddressingCycle, EmulationError> {
+    if !instruction.page_cross_cycle || cpu.page_crossed {
+        let unfixed_address = if cpu.page_crossed {
+            cpu.address_absolute.wrapping_sub(0x0100)
+        } else {
+            cpu.address_absolute
+        };
+        cpu.dummy_read(bus, unfixed_address)?;
+        return Ok(AddressingCycle::Ready);
+    }
+    if instruction.opcode.memory_access() != MemoryAccess::Read {
+        cpu.fetched = cpu.read_byte(bus, cpu.address_absolute)?;
+        return Ok(AddressingCycle::Fetched);
+    }
+    cpu.poll_interrupts(bus);
+    cpu.fetched = cpu.read_byte(bus, cpu.address_absolute)?;
+    Ok(AddressingCycle::Operand)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::bus::Bus;
+    use crate::cpu::instruction::{to_instruction, INSTRUCTIONS};
+    use crate::cpu::variant::Variant;
+    use crate::traits::write::Write;
+
+    /// A bus with `operands` at $0200, where the program counter is left.
+    fn bus_with_operands(operands: &[u8]) -> Bus {
+        let mut bus = Bus::new();
+        for (offset, byte) in operands.iter().enumerate() {
+            bus.write(0x0200 + offset as u16, *byte);
         }
+        bus
     }
-}
\ No newline at end of file
+
+    /// Runs the addressing cycles of a store, which always end with the effective address.
+    fn resolve(bus: &mut Bus, mode: AddressingMode, x: u8, y: u8) -> (u16, bool) {
+        let mut cpu = Cpu6502::new();
+        cpu.program_counter = 0x0200;
+        cpu.x_register = x;
+        cpu.y_register = y;
+        let store = INSTRUCTIONS.iter()
+            .find(|instruction| instruction.addressing_mode == mode && instruction.opcode.memory_access() == MemoryAccess::Write)
+            .expect("no store uses this addressing mode");
+        let mut step = 1;
+        while mode.execute(&mut cpu, bus, step, store) == Ok(AddressingCycle::Pending) {
+            step += 1;
+        }
+        (cpu.address_absolute, cpu.page_crossed)
+    }
+
+    #[test]
+    fn zero_page_indexed_wraps_within_the_zero_page() {
+        let mut bus = bus_with_operands(&[0xF0]);
+        assert_eq!(resolve(&mut bus, AddressingMode::ZeroPageX, 0x20, 0), (0x0010, false));
+        assert_eq!(resolve(&mut bus, AddressingMode::ZeroPageY, 0, 0x0F), (0x00FF, false));
+    }
+
+    #[test]
+    fn absolute_indexed_reports_a_page_cross() {
+        let mut bus = bus_with_operands(&[0xF0, 0x12]);
+        assert_eq!(resolve(&mut bus, AddressingMode::Absolute, 0, 0), (0x12F0, false));
+        assert_eq!(resolve(&mut bus, AddressingMode::AbsoluteX, 0x05, 0), (0x12F5, false));
+        assert_eq!(resolve(&mut bus, AddressingMode::AbsoluteX, 0x20, 0), (0x1310, true));
+        assert_eq!(resolve(&mut bus, AddressingMode::AbsoluteY, 0, 0x10), (0x1300, true));
+    }
+
+    #[test]
+    fn indexed_indirect_wraps_the_pointer() {
+        let mut bus = bus_with_operands(&[0xFE]);
+        bus.write(0x00FF, 0x34);
+        bus.write(0x0000, 0x12);
+        assert_eq!(resolve(&mut bus, AddressingMode::IndirectX, 0x01, 0), (0x1234, false));
+    }
+
+    #[test]
+    fn indirect_indexed_adds_y_after_the_pointer() {
+        let mut bus = bus_with_operands(&[0x10]);
+        bus.write(0x0010, 0xF0);
+        bus.write(0x0011, 0x12);
+        assert_eq!(resolve(&mut bus, AddressingMode::IndirectY, 0, 0x05), (0x12F5, false));
+        assert_eq!(resolve(&mut bus, AddressingMode::IndirectY, 0, 0x10), (0x1300, true));
+    }
+
+    #[test]
+    fn immediate_reads_the_operand_byte() {
+        let mut bus = bus_with_operands(&[0x42]);
+        let mut cpu = Cpu6502::new();
+        cpu.program_counter = 0x0200;
+        let cycle = AddressingMode::Immediate.execute(&mut cpu, &mut bus, 1, to_instruction(0xA9, Variant::Ricoh2A03));
+        assert_eq!(cycle, Ok(AddressingCycle::Operand));
+        assert_eq!((cpu.fetched, cpu.program_counter), (0x42, 0x0201));
+    }
+}
@@ -1,95 +1,1221 @@
-use crate::bus::bus::Bus;
-use crate::cpu::instruction::to_instruction;
-use crate::traits::read::Read;
-use crate::traits::write::Write;
-use crate::cpu::addressing_mode::AddressingMode;
-use std::fs::read;
+use crate::cpu::instruction::{to_instruction, Instruction};
+use crate::traits::cpu_bus::CpuBus;
+use crate::cpu::addressing_mode::{AddressingCycle, AddressingMode};
+use crate::cpu::cpu_state::CpuState;
+use crate::cpu::emulation_error::EmulationError;
+use crate::cpu::jam_policy::JamPolicy;
+use crate::cpu::opcode::{MemoryAccess, Opcode};
+use crate::cpu::processor_status_register::ProcessorStatusRegister;
+use crate::cpu::step::{Executed, Step};
+use crate::cpu::trace::Tracer;
+use crate::cpu::variant::Variant;
 
+const STACK_BASE: u16 = 0x0100;
+const NMI_VECTOR: u16 = 0xFFFA;
+const RESET_VECTOR: u16 = 0xFFFC;
+const IRQ_VECTOR: u16 = 0xFFFE;
+
+/// The hardware sequences that share the BRK microcode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Interrupt {
+    Reset,
+    NonMaskable,
+    Request,
+}
+
+/// A cycle-stepped 6502. Every call to `clock` performs the single bus read or write the real
+/// processor makes on that cycle, including dummy reads and the double write of
+/// read-modify-write instructions, so devices observe accesses at the right time.
+///
+/// The CPU does not own or borrow the bus; whatever owns both hands it the bus on each call.
 #[derive(Debug)]
-pub struct Cpu6502<'a>{
-    bus: &'a Bus<'a>,
-    accumulator: u8,
-    x_register: u8,
-    y_register: u8,
-    stack_pointer: u8,
-    program_counter: u16,
-    status_register: u8,
-    address_absolute: u16,
-    address_relative: u16,
-    opcode: u8,
-    cycles: u8,
-    fetched: u8
+pub struct Cpu6502 {
+    pub(crate) accumulator: u8,
+    pub(crate) x_register: u8,
+    pub(crate) y_register: u8,
+    pub(crate) stack_pointer: u8,
+    pub(crate) program_counter: u16,
+    pub(crate) status_register: ProcessorStatusRegister,
+    pub(crate) address_absolute: u16,
+    pub(crate) address_relative: u16,
+    pub(crate) pointer: u8,
+    pub(crate) page_crossed: bool,
+    pub(crate) opcode: u8,
+    pub(crate) fetched: u8,
+    step: u8,
+    access_start: u8,
+    extra_cycles: u8,
+    total_cycles: u64,
+    executed: Executed,
+    variant: Variant,
+    pending_variant: Option<Variant>,
+    jam_policy: JamPolicy,
+    pub(crate) jammed: bool,
+    pub(crate) waiting: bool,
+    interrupt: Option<Interrupt>,
+    interrupt_poll: bool,
+    nmi_previous: bool,
+    nmi_pending: bool,
+    irq_signal: bool,
+    reset_pending: bool,
+    tracer: Option<Tracer>
 }
 
-impl<'a> Cpu6502<'a> {
-    pub fn new(bus: &'a Bus<'a>) -> Cpu6502<'a> {
+impl Cpu6502 {
+    pub fn new() -> Cpu6502 {
         Cpu6502 {
-            bus,
             accumulator: 0,
             x_register: 0,
             y_register: 0,
             stack_pointer: 0,
             program_counter: 0,
-            status_register: 0,
+            status_register: ProcessorStatusRegister::default(),
             address_absolute: 0,
             address_relative: 0,
+            pointer: 0,
+            page_crossed: false,
             opcode: 0,
-            cycles: 0,
-            fetched: 0
+            fetched: 0,
+            step: 0,
+            access_start: 0,
+            extra_cycles: 0,
+            total_cycles: 0,
+            executed: Executed::Reset,
+            variant: Variant::default(),
+            pending_variant: None,
+            jam_policy: JamPolicy::default(),
+            jammed: false,
+            waiting: false,
+            interrupt: None,
+            interrupt_poll: false,
+            nmi_previous: false,
+            nmi_pending: false,
+            irq_signal: false,
+            reset_pending: false,
+            tracer: None
+        }
+    }
+
+    /// Runs a single CPU cycle. If the cycle fails the instruction is abandoned, and the next
+    /// clock starts a new one at the program counter.
+    pub fn clock(&mut self, bus: &mut dyn CpuBus) -> Result<(), EmulationError> {
+        self.poll_nmi_line(bus);
+        let result = if self.step == 0 {
+            if let Some(variant) = self.pending_variant.take() {
+                self.variant = variant;
+            }
+            self.begin_instruction(bus)
+        } else {
+            self.execute_cycle(bus)
+        };
+        self.total_cycles += 1;
+        bus.tick();
+        match result {
+            Ok(complete) => {
+                self.step = if complete { 0 } else { self.step + 1 };
+                Ok(())
+            },
+            Err(error) => {
+                self.step = 0;
+                Err(error)
+            },
+        }
+    }
+
+    /// Runs the next instruction or interrupt sequence to completion. Called mid-instruction,
+    /// it only finishes the instruction in progress and reports that one.
+    pub fn step_instruction(&mut self, bus: &mut dyn CpuBus) -> Result<Step, EmulationError> {
+        let start = self.total_cycles;
+        if self.step == 0 {
+            self.clock(bus)?;
         }
+        while self.step != 0 {
+            self.clock(bus)?;
+        }
+        Ok(Step {
+            executed: self.executed,
+            cycles: self.total_cycles - start,
+        })
     }
 
-    pub fn clock(&mut self){
-        if self.cycles == 0 {
-            self.opcode = self.read(self.program_counter)
-                .expect("Program counter address out of bounds");
-            self.program_counter += 1;
+    /// Logs every instruction to `tracer` before it is fetched, or stops tracing when `None`.
+    pub fn set_tracer(&mut self, tracer: Option<Tracer>) {
+        self.tracer = tracer;
+    }
 
-            let instruction = to_instruction(self.opcode);
-            self.cycles = instruction.cycles;
-            let additional_addressing_clock_cycle = instruction.addressing_mode.execute(self);
-            let additional_execution_clock_cycle = instruction.opcode.execute(self);
-            self.cycles += additional_addressing_clock_cycle + additional_execution_clock_cycle;
+    pub fn run_cycles(&mut self, bus: &mut dyn CpuBus, cycles: u64) -> Result<(), EmulationError> {
+        for _ in 0..cycles {
+            self.clock(bus)?;
         }
-        self.cycles -= 1;
+        Ok(())
     }
 
-    pub fn fetch(&mut self) -> u8 {
-        if to_instruction(self.opcode).addressing_mode != AddressingMode::Implied{
-            self.fetched = self.read(self.address_absolute)
-                .expect("Program counter address out of bounds");
+    /// Steps whole instructions until `predicate` holds, checking it before each one. Returns
+    /// the number of cycles run.
+    pub fn run_until<F>(&mut self, bus: &mut dyn CpuBus, mut predicate: F) -> Result<u64, EmulationError>
+        where F: FnMut(&Cpu6502, &dyn CpuBus) -> bool {
+        let start = self.total_cycles;
+        while !predicate(self, bus) {
+            self.step_instruction(bus)?;
         }
-        self.fetched
+        Ok(self.total_cycles - start)
     }
 
-    pub fn reset(){}
-    pub fn interrupt_request_signal(){}
-    pub fn non_maskable_interrupt_request_signal(){}
-}
+    /// The first cycle of every instruction: fetches the next opcode, or starts a pending reset
+    /// or interrupt sequence in its place. Returns true if nothing further needs to run.
+    fn begin_instruction(&mut self, bus: &mut dyn CpuBus) -> Result<bool, EmulationError> {
+        self.access_start = 0;
+        self.extra_cycles = 0;
+        self.page_crossed = false;
+        self.interrupt = None;
+
+        if self.reset_pending {
+            self.reset_pending = false;
+            self.interrupt = Some(Interrupt::Reset);
+            self.executed = Executed::Reset;
+            self.read_byte(bus, self.program_counter)?;
+            return Ok(false);
+        }
+        if self.jammed {
+            // A jammed CPU ignores interrupts and keeps reading $FFFF until it is reset.
+            self.read_byte(bus, 0xFFFF)?;
+            return Ok(true);
+        }
+        if self.waiting {
+            // WAI resumes on any interrupt, but only services an IRQ if the I flag allows it.
+            if !self.nmi_pending && !bus.irq_line() && !self.irq_signal {
+                self.read_byte(bus, self.program_counter)?;
+                return Ok(true);
+            }
+            self.waiting = false;
+            self.poll_interrupts(bus);
+        }
+        if self.interrupt_poll {
+            self.interrupt_poll = false;
+            if self.nmi_pending {
+                self.interrupt = Some(Interrupt::NonMaskable);
+                self.executed = Executed::NonMaskableInterrupt;
+            } else {
+                self.irq_signal = false;
+                self.interrupt = Some(Interrupt::Request);
+                self.executed = Executed::InterruptRequest;
+            }
+            // The opcode fetch still happens, but the program counter is not advanced.
+            self.read_byte(bus, self.program_counter)?;
+            return Ok(false);
+        }
+
+        // A trace sink that stops accepting lines ends tracing, not the emulation.
+        if let Some(mut tracer) = self.tracer.take() {
+            if tracer.trace(self, bus).is_ok() {
+                self.tracer = Some(tracer);
+            }
+        }
+        self.opcode = self.read_byte(bus, self.program_counter)?;
+        self.executed = Executed::Instruction { program_counter: self.program_counter, opcode: self.opcode };
+        self.program_counter = self.program_counter.wrapping_add(1);
+        // The 65C02's single-byte NOPs complete on their opcode fetch.
+        Ok(self.instruction().cycles == 1)
+    }
+
+    /// Runs cycle `step` (counting from 1 after the opcode fetch) of the current instruction.
+    /// Returns true on the instruction's last cycle.
+    fn execute_cycle(&mut self, bus: &mut dyn CpuBus) -> Result<bool, EmulationError> {
+        if self.interrupt.is_some() {
+            return self.interrupt_cycle(bus);
+        }
+        let instruction = self.instruction();
+        match instruction.opcode {
+            Opcode::BRK => self.interrupt_cycle(bus),
+            Opcode::JSR => self.jump_to_subroutine_cycle(bus),
+            Opcode::RTS => self.return_from_subroutine_cycle(bus),
+            Opcode::RTI => self.return_from_interrupt_cycle(bus),
+            Opcode::PHA | Opcode::PHP | Opcode::PHX | Opcode::PHY => self.push_cycle(bus, instruction),
+            Opcode::PLA | Opcode::PLP | Opcode::PLX | Opcode::PLY => self.pull_cycle(bus, instruction),
+            Opcode::JMP => self.jump_cycle(bus, instruction),
+            Opcode::BBR | Opcode::BBS => self.bit_branch_cycle(bus, instruction),
+            Opcode::JAM if self.jam_policy != JamPolicy::Nop => self.jam_cycle(bus),
+            _ if instruction.addressing_mode == AddressingMode::Relative => {
+                let taken = self.step > 1 || instruction.opcode.branch_taken(self);
+                self.branch_cycle(bus, self.step, taken)
+            },
+            _ => self.memory_cycle(bus, instruction),
+        }
+    }
+
+    fn jam_cycle(&mut self, bus: &mut dyn CpuBus) -> Result<bool, EmulationError> {
+        self.read_byte(bus, self.program_counter)?;
+        self.jammed = true;
+        if self.jam_policy == JamPolicy::Report {
+            return Err(EmulationError::IllegalOpcode {
+                program_counter: self.program_counter.wrapping_sub(1),
+                opcode: self.opcode,
+            });
+        }
+        Ok(true)
+    }
 
-impl Read<u16, u8> for Cpu6502<'_> {
-    fn read(&self, address: u16) -> Option<u8>{
-        self.bus.read(address)
+    /// Instructions that operate on registers or on a memory operand: the addressing mode runs
+    /// first, then the read, write or read-modify-write of the effective address.
+    fn memory_cycle(&mut self, bus: &mut dyn CpuBus, instruction: &Instruction) -> Result<bool, EmulationError> {
+        if self.extra_cycles > 0 {
+            self.extra_cycles -= 1;
+            self.poll_interrupts(bus);
+            self.read_byte(bus, self.program_counter.wrapping_sub(1))?;
+            return Ok(self.extra_cycles == 0);
+        }
+
+        let access = instruction.opcode.memory_access();
+        if self.access_start == 0 {
+            return Ok(match instruction.addressing_mode.execute(self, bus, self.step, instruction)? {
+                AddressingCycle::Pending => false,
+                AddressingCycle::Ready => {
+                    self.access_start = self.step + 1;
+                    false
+                },
+                AddressingCycle::Fetched => {
+                    self.access_start = self.step;
+                    false
+                },
+                AddressingCycle::Operand => {
+                    instruction.opcode.execute(self);
+                    if instruction.addressing_mode == AddressingMode::Accumulator {
+                        self.accumulator = self.fetched;
+                    }
+                    self.finish(instruction)
+                },
+            });
+        }
+
+        Ok(match (access, self.step - self.access_start) {
+            (MemoryAccess::Read, _) => {
+                self.poll_interrupts(bus);
+                self.fetched = self.read_byte(bus, self.address_absolute)?;
+                instruction.opcode.execute(self);
+                self.finish(instruction)
+            },
+            (MemoryAccess::Write, _) => {
+                self.poll_interrupts(bus);
+                instruction.opcode.execute(self);
+                self.write_byte(bus, self.address_absolute, self.fetched)?;
+                true
+            },
+            (MemoryAccess::ReadModifyWrite, 0) => {
+                self.fetched = self.read_byte(bus, self.address_absolute)?;
+                false
+            },
+            (MemoryAccess::ReadModifyWrite, 1) => {
+                // The NMOS parts write the unmodified value back while the ALU works on it; the
+                // 65C02 reads it a second time instead.
+                if self.variant == Variant::Wdc65C02 {
+                    self.read_byte(bus, self.address_absolute)?;
+                } else {
+                    self.write_byte(bus, self.address_absolute, self.fetched)?;
+                }
+                instruction.opcode.execute(self);
+                false
+            },
+            (MemoryAccess::ReadModifyWrite, _) => {
+                self.poll_interrupts(bus);
+                self.write_byte(bus, self.address_absolute, self.fetched)?;
+                true
+            },
+        })
+    }
+
+    /// Works out whether an instruction that has done its work still has idle cycles to run:
+    /// the 65C02 takes an extra cycle for decimal ADC and SBC, and a few of its reserved NOPs
+    /// run for longer than their addressing mode needs. Returns true if there are none.
+    fn finish(&mut self, instruction: &Instruction) -> bool {
+        let decimal = self.variant == Variant::Wdc65C02
+            && self.decimal_mode()
+            && matches!(instruction.opcode, Opcode::ADC | Opcode::SBC);
+        let remaining = instruction.cycles.saturating_sub(self.step + 1);
+        self.extra_cycles = remaining + decimal as u8;
+        self.extra_cycles == 0
+    }
+
+    /// A taken branch costs one extra cycle, and another if the target is on a different page.
+    /// A taken branch that stays on its page does not poll for interrupts on its last cycle.
+    /// `step` counts from the cycle that reads the offset.
+    fn branch_cycle(&mut self, bus: &mut dyn CpuBus, step: u8, taken: bool) -> Result<bool, EmulationError> {
+        Ok(match step {
+            1 => {
+                self.poll_interrupts(bus);
+                self.address_relative = self.read_program_counter(bus)? as i8 as u16;
+                !taken
+            },
+            2 => {
+                self.read_byte(bus, self.program_counter)?;
+                let target = self.program_counter.wrapping_add(self.address_relative);
+                if target & 0xFF00 == self.program_counter & 0xFF00 {
+                    self.program_counter = target;
+                    true
+                } else {
+                    self.program_counter = (self.program_counter & 0xFF00) | (target & 0x00FF);
+                    self.address_absolute = target;
+                    false
+                }
+            },
+            _ => {
+                self.poll_interrupts(bus);
+                self.read_byte(bus, self.program_counter)?;
+                self.program_counter = self.address_absolute;
+                true
+            },
+        })
+    }
+
+    /// BBR and BBS read and test a zero page byte, then branch like any other branch.
+    fn bit_branch_cycle(&mut self, bus: &mut dyn CpuBus, instruction: &Instruction) -> Result<bool, EmulationError> {
+        Ok(match self.step {
+            1 => {
+                self.address_absolute = self.read_program_counter(bus)? as u16;
+                false
+            },
+            2 => {
+                self.fetched = self.read_byte(bus, self.address_absolute)?;
+                false
+            },
+            3 => {
+                self.read_byte(bus, self.address_absolute)?;
+                false
+            },
+            step => {
+                let taken = step > 4 || instruction.opcode.branch_taken(self);
+                self.branch_cycle(bus, step - 3, taken)?
+            },
+        })
+    }
+
+    fn jump_cycle(&mut self, bus: &mut dyn CpuBus, instruction: &Instruction) -> Result<bool, EmulationError> {
+        let cmos = self.variant == Variant::Wdc65C02;
+        Ok(match (instruction.addressing_mode, self.step) {
+            (_, 1) => {
+                self.address_absolute = self.read_program_counter(bus)? as u16;
+                false
+            },
+            (AddressingMode::Absolute, _) => {
+                self.poll_interrupts(bus);
+                let high = self.read_byte(bus, self.program_counter)? as u16;
+                self.program_counter = (high << 8) | self.address_absolute;
+                true
+            },
+            (_, 2) => {
+                self.address_absolute |= (self.read_program_counter(bus)? as u16) << 8;
+                false
+            },
+            // The 65C02 spends an extra cycle on the pointer, which is where JMP ($nnnn,X) adds X.
+            (_, 3) if cmos => {
+                self.read_byte(bus, self.program_counter.wrapping_sub(1))?;
+                if instruction.addressing_mode == AddressingMode::AbsoluteIndexedIndirect {
+                    self.address_absolute = self.address_absolute.wrapping_add(self.x_register as u16);
+                }
+                false
+            },
+            (_, step) if step == 3 + cmos as u8 => {
+                self.fetched = self.read_byte(bus, self.address_absolute)?;
+                false
+            },
+            _ => {
+                self.poll_interrupts(bus);
+                // The NMOS 6502 does not carry into the high byte when fetching the target, so
+                // JMP ($xxFF) reads its high byte from $xx00 rather than the next page. The 65C02
+                // fixes this.
+                let high_pointer = if cmos {
+                    self.address_absolute.wrapping_add(1)
+                } else {
+                    (self.address_absolute & 0xFF00) | (self.address_absolute.wrapping_add(1) & 0x00FF)
+                };
+                let high = self.read_byte(bus, high_pointer)? as u16;
+                self.program_counter = (high << 8) | self.fetched as u16;
+                true
+            },
+        })
+    }
+
+    fn jump_to_subroutine_cycle(&mut self, bus: &mut dyn CpuBus) -> Result<bool, EmulationError> {
+        Ok(match self.step {
+            1 => {
+                self.address_absolute = self.read_program_counter(bus)? as u16;
+                false
+            },
+            2 => {
+                self.read_byte(bus, STACK_BASE + self.stack_pointer as u16)?;
+                false
+            },
+            // JSR pushes the address of its own last byte; RTS adds the missing one back.
+            3 => {
+                self.push(bus, (self.program_counter >> 8) as u8)?;
+                false
+            },
+            4 => {
+                self.push(bus, self.program_counter as u8)?;
+                false
+            },
+            _ => {
+                self.poll_interrupts(bus);
+                let high = self.read_byte(bus, self.program_counter)? as u16;
+                self.program_counter = (high << 8) | self.address_absolute;
+                true
+            },
+        })
     }
 
-    fn read_only(&self, address: u16) -> Option<u8>{
-        self.bus.read_only(address)
+    fn return_from_subroutine_cycle(&mut self, bus: &mut dyn CpuBus) -> Result<bool, EmulationError> {
+        Ok(match self.step {
+            1 => {
+                self.read_byte(bus, self.program_counter)?;
+                false
+            },
+            2 => {
+                self.read_byte(bus, STACK_BASE + self.stack_pointer as u16)?;
+                self.stack_pointer = self.stack_pointer.wrapping_add(1);
+                false
+            },
+            3 => {
+                let low = self.read_byte(bus, STACK_BASE + self.stack_pointer as u16)? as u16;
+                self.program_counter = (self.program_counter & 0xFF00) | low;
+                self.stack_pointer = self.stack_pointer.wrapping_add(1);
+                false
+            },
+            4 => {
+                let high = self.read_byte(bus, STACK_BASE + self.stack_pointer as u16)? as u16;
+                self.program_counter = (high << 8) | (self.program_counter & 0x00FF);
+                false
+            },
+            _ => {
+                self.poll_interrupts(bus);
+                self.read_byte(bus, self.program_counter)?;
+                self.program_counter = self.program_counter.wrapping_add(1);
+                true
+            },
+        })
+    }
+
+    fn return_from_interrupt_cycle(&mut self, bus: &mut dyn CpuBus) -> Result<bool, EmulationError> {
+        Ok(match self.step {
+            1 => {
+                self.read_byte(bus, self.program_counter)?;
+                false
+            },
+            2 => {
+                self.read_byte(bus, STACK_BASE + self.stack_pointer as u16)?;
+                self.stack_pointer = self.stack_pointer.wrapping_add(1);
+                false
+            },
+            3 => {
+                let status = self.read_byte(bus, STACK_BASE + self.stack_pointer as u16)?;
+                self.status_register = ProcessorStatusRegister::from_stack(status);
+                self.stack_pointer = self.stack_pointer.wrapping_add(1);
+                false
+            },
+            4 => {
+                let low = self.read_byte(bus, STACK_BASE + self.stack_pointer as u16)? as u16;
+                self.program_counter = (self.program_counter & 0xFF00) | low;
+                self.stack_pointer = self.stack_pointer.wrapping_add(1);
+                false
+            },
+            _ => {
+                self.poll_interrupts(bus);
+                let high = self.read_byte(bus, STACK_BASE + self.stack_pointer as u16)? as u16;
+                self.program_counter = (high << 8) | (self.program_counter & 0x00FF);
+                true
+            },
+        })
+    }
+
+    fn push_cycle(&mut self, bus: &mut dyn CpuBus, instruction: &Instruction) -> Result<bool, EmulationError> {
+        if self.step == 1 {
+            self.read_byte(bus, self.program_counter)?;
+            return Ok(false);
+        }
+        self.poll_interrupts(bus);
+        instruction.opcode.execute(self);
+        self.push(bus, self.fetched)?;
+        Ok(true)
+    }
+
+    fn pull_cycle(&mut self, bus: &mut dyn CpuBus, instruction: &Instruction) -> Result<bool, EmulationError> {
+        Ok(match self.step {
+            1 => {
+                self.read_byte(bus, self.program_counter)?;
+                false
+            },
+            2 => {
+                self.read_byte(bus, STACK_BASE + self.stack_pointer as u16)?;
+                self.stack_pointer = self.stack_pointer.wrapping_add(1);
+                false
+            },
+            _ => {
+                self.poll_interrupts(bus);
+                self.fetched = self.read_byte(bus, STACK_BASE + self.stack_pointer as u16)?;
+                instruction.opcode.execute(self);
+                true
+            },
+        })
+    }
+
+    /// BRK, IRQ, NMI and reset all run the same seven cycles. BRK skips its padding byte and
+    /// pushes B set; IRQ and NMI push B clear; reset performs reads instead of the three
+    /// pushes. The vector is chosen on the cycle P is pushed, so an NMI that arrives while BRK
+    /// or IRQ is still pushing hijacks the sequence and enters the NMI handler instead.
+    fn interrupt_cycle(&mut self, bus: &mut dyn CpuBus) -> Result<bool, EmulationError> {
+        let reset = self.interrupt == Some(Interrupt::Reset);
+        Ok(match self.step {
+            1 => {
+                self.read_byte(bus, self.program_counter)?;
+                if self.interrupt.is_none() {
+                    self.program_counter = self.program_counter.wrapping_add(1);
+                }
+                false
+            },
+            2..=4 => {
+                let data = match self.step {
+                    2 => (self.program_counter >> 8) as u8,
+                    3 => self.program_counter as u8,
+                    _ => self.status_register.to_stack(self.interrupt.is_none()),
+                };
+                if reset {
+                    self.read_byte(bus, STACK_BASE + self.stack_pointer as u16)?;
+                    self.stack_pointer = self.stack_pointer.wrapping_sub(1);
+                } else {
+                    self.push(bus, data)?;
+                }
+                if self.step == 4 {
+                    self.address_absolute = if reset {
+                        RESET_VECTOR
+                    } else if self.nmi_pending {
+                        self.nmi_pending = false;
+                        NMI_VECTOR
+                    } else {
+                        IRQ_VECTOR
+                    };
+                }
+                false
+            },
+            5 => {
+                self.program_counter = self.read_byte(bus, self.address_absolute)? as u16;
+                self.set_flag(ProcessorStatusRegister::DISABLE_INTERRUPTS, true);
+                if self.variant == Variant::Wdc65C02 {
+                    self.set_flag(ProcessorStatusRegister::DECIMAL_MODE, false);
+                }
+                false
+            },
+            _ => {
+                let high = self.read_byte(bus, self.address_absolute.wrapping_add(1))? as u16;
+                self.program_counter |= high << 8;
+                true
+            },
+        })
+    }
+
+    /// NMI is edge triggered: only a transition of the line to asserted latches a request.
+    fn poll_nmi_line(&mut self, bus: &dyn CpuBus) {
+        let nmi_line = bus.nmi_line();
+        if nmi_line && !self.nmi_previous {
+            self.nmi_pending = true;
+        }
+        self.nmi_previous = nmi_line;
+    }
+
+    /// Samples the interrupt lines. The 6502 does this before the last cycle of each
+    /// instruction, so a change to the I flag by CLI, SEI or PLP only takes effect after the
+    /// following instruction.
+    pub(crate) fn poll_interrupts(&mut self, bus: &dyn CpuBus) {
+        let irq_line = bus.irq_line() || self.irq_signal;
+        let irq = irq_line && !self.get_flag(ProcessorStatusRegister::DISABLE_INTERRUPTS);
+        self.interrupt_poll = self.nmi_pending || irq;
+    }
+
+    /// The bus access made while the CPU adds an index register. The NMOS parts read from the
+    /// partially computed address; the 65C02 re-reads the last operand byte instead.
+    pub(crate) fn dummy_read(&mut self, bus: &mut dyn CpuBus, address: u16) -> Result<(), EmulationError> {
+        match self.variant {
+            Variant::Wdc65C02 => self.read_byte(bus, self.program_counter.wrapping_sub(1))?,
+            _ => self.read_byte(bus, address)?,
+        };
+        Ok(())
+    }
+
+    pub(crate) fn read_byte(&self, bus: &mut dyn CpuBus, address: u16) -> Result<u8, EmulationError> {
+        bus.read(address)
+            .ok_or(EmulationError::UnmappedRead { address })
+    }
+
+    pub(crate) fn read_program_counter(&mut self, bus: &mut dyn CpuBus) -> Result<u8, EmulationError> {
+        let data = self.read_byte(bus, self.program_counter)?;
+        self.program_counter = self.program_counter.wrapping_add(1);
+        Ok(data)
+    }
+
+    fn write_byte(&mut self, bus: &mut dyn CpuBus, address: u16, data: u8) -> Result<(), EmulationError> {
+        bus.try_write(address, data)
+    }
+
+    pub fn state(&self) -> CpuState {
+        CpuState {
+            accumulator: self.accumulator,
+            x_register: self.x_register,
+            y_register: self.y_register,
+            stack_pointer: self.stack_pointer,
+            program_counter: self.program_counter,
+            status_register: self.status_register,
+        }
+    }
+
+    pub fn set_state(&mut self, state: &CpuState) {
+        self.accumulator = state.accumulator;
+        self.x_register = state.x_register;
+        self.y_register = state.y_register;
+        self.stack_pointer = state.stack_pointer;
+        self.program_counter = state.program_counter;
+        self.status_register = state.status_register;
+    }
+
+    /// Number of clock cycles elapsed since the CPU was created.
+    pub fn total_cycles(&self) -> u64 {
+        self.total_cycles
+    }
+
+    pub fn set_total_cycles(&mut self, total_cycles: u64) {
+        self.total_cycles = total_cycles;
+    }
+
+    pub fn accumulator(&self) -> u8 {
+        self.accumulator
+    }
+
+    pub fn set_accumulator(&mut self, accumulator: u8) {
+        self.accumulator = accumulator;
+    }
+
+    pub fn x_register(&self) -> u8 {
+        self.x_register
+    }
+
+    pub fn set_x_register(&mut self, x_register: u8) {
+        self.x_register = x_register;
+    }
+
+    pub fn y_register(&self) -> u8 {
+        self.y_register
+    }
+
+    pub fn set_y_register(&mut self, y_register: u8) {
+        self.y_register = y_register;
+    }
+
+    pub fn stack_pointer(&self) -> u8 {
+        self.stack_pointer
+    }
+
+    pub fn set_stack_pointer(&mut self, stack_pointer: u8) {
+        self.stack_pointer = stack_pointer;
+    }
+
+    pub fn program_counter(&self) -> u16 {
+        self.program_counter
+    }
+
+    pub fn set_program_counter(&mut self, program_counter: u16) {
+        self.program_counter = program_counter;
+    }
+
+    pub fn status(&self) -> ProcessorStatusRegister {
+        self.status_register
+    }
+
+    pub fn set_status(&mut self, status: ProcessorStatusRegister) {
+        self.status_register = status;
+    }
+
+    pub fn get_flag(&self, flag: ProcessorStatusRegister) -> bool {
+        self.status_register.contains(flag)
+    }
+
+    pub fn set_flag(&mut self, flag: ProcessorStatusRegister, value: bool) {
+        self.status_register.set(flag, value);
+    }
+
+    pub(crate) fn set_zero_and_negative(&mut self, value: u8) {
+        self.set_flag(ProcessorStatusRegister::ZERO, value == 0);
+        self.set_flag(ProcessorStatusRegister::NEGATIVE, value & 0x80 != 0);
+    }
+
+    fn push(&mut self, bus: &mut dyn CpuBus, data: u8) -> Result<(), EmulationError> {
+        self.write_byte(bus, STACK_BASE + self.stack_pointer as u16, data)?;
+        self.stack_pointer = self.stack_pointer.wrapping_sub(1);
+        Ok(())
+    }
+
+    pub(crate) fn instruction(&self) -> &'static Instruction {
+        to_instruction(self.opcode, self.variant)
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Selects the chip to emulate, which decides how opcodes decode and whether the D flag
+    /// enables BCD arithmetic. Takes effect from the next instruction: called between
+    /// instructions it applies at once, and an instruction in progress finishes as the old chip.
+    pub fn set_variant(&mut self, variant: Variant) {
+        if self.step == 0 {
+            self.variant = variant;
+            self.pending_variant = None;
+        } else {
+            self.pending_variant = Some(variant);
+        }
+    }
+
+    pub fn jam_policy(&self) -> JamPolicy {
+        self.jam_policy
+    }
+
+    pub fn set_jam_policy(&mut self, jam_policy: JamPolicy) {
+        self.jam_policy = jam_policy;
+    }
+
+    /// Whether ADC and SBC currently work in BCD.
+    pub(crate) fn decimal_mode(&self) -> bool {
+        self.variant.has_decimal_mode() && self.get_flag(ProcessorStatusRegister::DECIMAL_MODE)
+    }
+
+    /// Whether a JAM opcode (or the 65C02's STP) has locked up the CPU. Only a reset recovers it.
+    pub fn is_jammed(&self) -> bool {
+        self.jammed
+    }
+
+    /// Pulls the reset line. The instruction in progress is abandoned and the reset sequence
+    /// runs over the next seven cycles: the stack pointer is decremented by three without
+    /// writing, IRQs are masked and execution continues at the address held in $FFFC/$FFFD.
+    pub fn reset(&mut self){
+        self.reset_pending = true;
+        self.step = 0;
+        self.jammed = false;
+        self.waiting = false;
+        self.nmi_pending = false;
+        self.interrupt_poll = false;
+        self.irq_signal = false;
+        self.set_flag(ProcessorStatusRegister::UNUSED, true);
+    }
+
+    /// Requests a maskable interrupt, held until it is serviced. Like an IRQ raised by a device
+    /// on the bus it is taken at the next interrupt poll while the I flag is clear.
+    pub fn interrupt_request_signal(&mut self){
+        self.irq_signal = true;
+    }
+
+    /// Latches a non-maskable interrupt, as if the NMI line had just been asserted.
+    pub fn non_maskable_interrupt_request_signal(&mut self){
+        self.nmi_pending = true;
     }
 }
 
-impl Write<u16, u8> for Cpu6502<'_> {
-    fn write(&self, address: u16, data: u8) {
-        self.bus.write(address, data)
+impl Default for Cpu6502 {
+    fn default() -> Self {
+        Cpu6502::new()
     }
 }
 
-enum ProcessorStatusRegister {
-    Carry = 0x01,
-    Zero = 0x02,
-    DisableInterrupts = 0x03,
-    DecimalModel = 0x04,
-    Break = 0x05,
-    Overflow = 0x07,
-    Negative = 0x08
+impl Clone for Cpu6502 {
+    /// A clone is a snapshot of the processor state. It does not inherit the tracer, whose sink
+    /// cannot be shared.
+    fn clone(&self) -> Self {
+        Cpu6502 {
+            tracer: None,
+            ..*self
+        }
+    }
 }
 
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::traits::read::Read;
+    use crate::traits::write::Write;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Access {
+        Read(u16),
+        Write(u16, u8),
+    }
+
+    /// 64KB of RAM with interrupt lines driven by the test, which logs every bus access the CPU
+    /// makes. The reset vector points at $8000, NMI at $9000 and IRQ/BRK at $A000; everything
+    /// else is NOP.
+    #[derive(Debug)]
+    pub(crate) struct TestBus {
+        memory: Vec<u8>,
+        accesses: Vec<Access>,
+        irq: bool,
+        nmi: bool
+    }
+
+    impl TestBus {
+        pub(crate) fn with_program(program: &[u8]) -> Self {
+            let mut bus = TestBus {
+                memory: vec![0xEA; 0x10000],
+                accesses: Vec::new(),
+                irq: false,
+                nmi: false
+            };
+            bus.load(0x0200, program);
+            bus.load(0xFFFA, &[0x00, 0x90, 0x00, 0x80, 0x00, 0xA0]);
+            bus
+        }
+
+        pub(crate) fn load(&mut self, address: u16, bytes: &[u8]) {
+            let start = address as usize;
+            self.memory[start..start + bytes.len()].copy_from_slice(bytes);
+        }
+
+        fn take_accesses(&mut self) -> Vec<Access> {
+            std::mem::take(&mut self.accesses)
+        }
+    }
+
+    impl CpuBus for TestBus {
+        fn irq_line(&self) -> bool {
+            self.irq
+        }
+
+        fn nmi_line(&self) -> bool {
+            self.nmi
+        }
+    }
+
+    impl Read<u16, u8> for TestBus {
+        fn read(&mut self, address: u16) -> Option<u8> {
+            self.accesses.push(Access::Read(address));
+            self.read_only(address)
+        }
+
+        fn read_only(&self, address: u16) -> Option<u8> {
+            self.memory.get(address as usize).copied()
+        }
+    }
+
+    impl Write<u16, u8> for TestBus {
+        fn write(&mut self, address: u16, data: u8) {
+            self.accesses.push(Access::Write(address, data));
+            if let Some(byte) = self.memory.get_mut(address as usize) {
+                *byte = data;
+            }
+        }
+    }
+
+    /// 32KB of RAM at $0000-$7FFF and nothing above it: reads there are unmapped and writes
+    /// are rejected.
+    #[derive(Debug)]
+    struct PartialBus {
+        memory: Vec<u8>
+    }
+
+    impl PartialBus {
+        fn with_program(program: &[u8]) -> Self {
+            let mut memory = vec![0xEA; 0x8000];
+            memory[0x0200..0x0200 + program.len()].copy_from_slice(program);
+            PartialBus { memory }
+        }
+    }
+
+    impl CpuBus for PartialBus {
+        fn try_write(&mut self, address: u16, data: u8) -> Result<(), EmulationError> {
+            if address >= 0x8000 {
+                return Err(EmulationError::BusFault { address });
+            }
+            self.write(address, data);
+            Ok(())
+        }
+    }
+
+    impl Read<u16, u8> for PartialBus {
+        fn read(&mut self, address: u16) -> Option<u8> {
+            self.read_only(address)
+        }
+
+        fn read_only(&self, address: u16) -> Option<u8> {
+            self.memory.get(address as usize).copied()
+        }
+    }
+
+    impl Write<u16, u8> for PartialBus {
+        fn write(&mut self, address: u16, data: u8) {
+            if let Some(byte) = self.memory.get_mut(address as usize) {
+                *byte = data;
+            }
+        }
+    }
+
+    fn cpu_at_program() -> Cpu6502 {
+        let mut cpu = Cpu6502::new();
+        cpu.program_counter = 0x0200;
+        cpu.stack_pointer = 0xFD;
+        cpu
+    }
+
+    fn pushed_status(bus: &TestBus) -> u8 {
+        bus.read_only(0x01FB).unwrap()
+    }
+
+    #[test]
+    fn reset_loads_the_vector_and_skips_the_stack_writes() {
+        let mut bus = TestBus::with_program(&[]);
+        bus.load(0x01FB, &[0x55; 3]);
+        let mut cpu = cpu_at_program();
+        cpu.reset();
+        let step = cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(step.executed, Executed::Reset);
+        assert_eq!(step.cycles, 7);
+        assert_eq!(cpu.program_counter, 0x8000);
+        assert_eq!(cpu.stack_pointer, 0xFA);
+        assert!(cpu.get_flag(ProcessorStatusRegister::DISABLE_INTERRUPTS));
+        assert_eq!(&bus.memory[0x01FB..0x01FE], &[0x55; 3]);
+    }
+
+    #[test]
+    fn irq_is_held_off_until_the_instruction_after_cli() {
+        // CLI; NOP
+        let mut bus = TestBus::with_program(&[0x58, 0xEA]);
+        bus.irq = true;
+        let mut cpu = cpu_at_program();
+        cpu.set_flag(ProcessorStatusRegister::DISABLE_INTERRUPTS, true);
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0x0201);
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0x0202);
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0xA000);
+        assert_eq!(bus.read_only(0x01FD), Some(0x02));
+        assert_eq!(bus.read_only(0x01FC), Some(0x02));
+        assert!(cpu.get_flag(ProcessorStatusRegister::DISABLE_INTERRUPTS));
+
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0xA001);
+    }
+
+    #[test]
+    fn irq_pending_during_sei_is_still_taken() {
+        // SEI; NOP. The IRQ is sampled before SEI sets I on its last cycle.
+        let mut bus = TestBus::with_program(&[0x78, 0xEA]);
+        bus.irq = true;
+        let mut cpu = cpu_at_program();
+        cpu.step_instruction(&mut bus).unwrap();
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0xA000);
+        assert_eq!(bus.read_only(0x01FC), Some(0x01));
+        assert!(pushed_status(&bus) & ProcessorStatusRegister::DISABLE_INTERRUPTS.bits() != 0);
+    }
+
+    #[test]
+    fn nmi_fires_once_per_edge() {
+        let mut bus = TestBus::with_program(&[]);
+        bus.nmi = true;
+        let mut cpu = cpu_at_program();
+        // The edge is latched during the NOP at $0200 and serviced after it.
+        cpu.step_instruction(&mut bus).unwrap();
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0x9000);
+        assert_eq!(cpu.stack_pointer, 0xFA);
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0x9001);
+
+        bus.nmi = false;
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0x9002);
+        bus.nmi = true;
+        cpu.step_instruction(&mut bus).unwrap();
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0x9000);
+        assert_eq!(cpu.stack_pointer, 0xF7);
+    }
+
+    #[test]
+    fn nmi_during_brk_hijacks_the_vector() {
+        let mut bus = TestBus::with_program(&[0x00, 0x00]);
+        let mut cpu = cpu_at_program();
+        cpu.clock(&mut bus).unwrap();
+        bus.nmi = true;
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0x9000);
+        assert_eq!(bus.read_only(0x01FC), Some(0x02));
+        assert!(pushed_status(&bus) & ProcessorStatusRegister::BREAK.bits() != 0);
+
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0x9001);
+    }
+
+    #[test]
+    fn pushed_break_flag_tells_brk_apart_from_interrupts() {
+        let mut bus = TestBus::with_program(&[0x00, 0x00]);
+        let mut cpu = cpu_at_program();
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0xA000);
+        assert!(pushed_status(&bus) & ProcessorStatusRegister::BREAK.bits() != 0);
+
+        let mut bus = TestBus::with_program(&[]);
+        bus.irq = true;
+        let mut cpu = cpu_at_program();
+        cpu.step_instruction(&mut bus).unwrap();
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0xA000);
+        assert_eq!(pushed_status(&bus) & ProcessorStatusRegister::BREAK.bits(), 0);
+
+        let mut bus = TestBus::with_program(&[]);
+        bus.nmi = true;
+        let mut cpu = cpu_at_program();
+        cpu.step_instruction(&mut bus).unwrap();
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.program_counter, 0x9000);
+        assert_eq!(pushed_status(&bus) & ProcessorStatusRegister::BREAK.bits(), 0);
+    }
+
+    #[test]
+    fn step_instruction_mid_instruction_only_finishes_it() {
+        // LDA #$01; NOP
+        let mut bus = TestBus::with_program(&[0xA9, 0x01, 0xEA]);
+        let mut cpu = cpu_at_program();
+        cpu.clock(&mut bus).unwrap();
+        let step = cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(step.executed, Executed::Instruction { program_counter: 0x0200, opcode: 0xA9 });
+        assert_eq!(step.cycles, 1);
+        assert_eq!(cpu.accumulator, 0x01);
+
+        let step = cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(step.executed, Executed::Instruction { program_counter: 0x0202, opcode: 0xEA });
+        assert_eq!(step.cycles, 2);
+    }
+
+    /// Runs one instruction from $0200 and returns the bus accesses it made.
+    fn accesses(bus: &mut TestBus, x_register: u8, y_register: u8) -> Vec<Access> {
+        let mut cpu = cpu_at_program();
+        cpu.x_register = x_register;
+        cpu.y_register = y_register;
+        cpu.accumulator = 0x42;
+        cpu.step_instruction(bus).unwrap();
+        bus.take_accesses()
+    }
+
+    #[test]
+    fn absolute_indexed_read_fixes_up_a_page_cross() {
+        use Access::Read;
+        // LDA $12F0,X
+        let mut bus = TestBus::with_program(&[0xBD, 0xF0, 0x12]);
+        assert_eq!(accesses(&mut bus, 0x05, 0), [Read(0x0200), Read(0x0201), Read(0x0202), Read(0x12F5)]);
+        assert_eq!(
+            accesses(&mut bus, 0x20, 0),
+            [Read(0x0200), Read(0x0201), Read(0x0202), Read(0x1210), Read(0x1310)]
+        );
+    }
+
+    #[test]
+    fn indirect_indexed_read_fixes_up_a_page_cross() {
+        use Access::Read;
+        // LDA ($10),Y
+        let mut bus = TestBus::with_program(&[0xB1, 0x10]);
+        bus.load(0x0010, &[0xF0, 0x12]);
+        assert_eq!(
+            accesses(&mut bus, 0, 0x05),
+            [Read(0x0200), Read(0x0201), Read(0x0010), Read(0x0011), Read(0x12F5)]
+        );
+        assert_eq!(
+            accesses(&mut bus, 0, 0x20),
+            [Read(0x0200), Read(0x0201), Read(0x0010), Read(0x0011), Read(0x1210), Read(0x1310)]
+        );
+    }
+
+    #[test]
+    fn indexed_store_always_makes_a_dummy_read() {
+        use Access::{Read, Write};
+        // STA $12F0,X
+        let mut bus = TestBus::with_program(&[0x9D, 0xF0, 0x12]);
+        assert_eq!(
+            accesses(&mut bus, 0x05, 0),
+            [Read(0x0200), Read(0x0201), Read(0x0202), Read(0x12F5), Write(0x12F5, 0x42)]
+        );
+        assert_eq!(
+            accesses(&mut bus, 0x20, 0),
+            [Read(0x0200), Read(0x0201), Read(0x0202), Read(0x1210), Write(0x1310, 0x42)]
+        );
+    }
+
+    #[test]
+    fn read_modify_write_writes_the_old_value_back_first() {
+        use Access::{Read, Write};
+        // INC $12F0,X
+        let mut bus = TestBus::with_program(&[0xFE, 0xF0, 0x12]);
+        bus.load(0x12F5, &[0x00]);
+        assert_eq!(
+            accesses(&mut bus, 0x05, 0),
+            [
+                Read(0x0200), Read(0x0201), Read(0x0202), Read(0x12F5), Read(0x12F5),
+                Write(0x12F5, 0x00), Write(0x12F5, 0x01)
+            ]
+        );
+    }
+
+    #[test]
+    fn variant_change_waits_for_the_next_instruction() {
+        // SED; ARR #$FF with A = $FF, which only the NMOS part adjusts in decimal mode.
+        let mut bus = TestBus::with_program(&[0xF8, 0x6B, 0xFF]);
+        let mut cpu = cpu_at_program();
+        cpu.set_variant(Variant::Nmos6502);
+        cpu.set_accumulator(0xFF);
+        cpu.step_instruction(&mut bus).unwrap();
+        cpu.clock(&mut bus).unwrap();
+        cpu.set_variant(Variant::Ricoh2A03);
+        assert_eq!(cpu.variant(), Variant::Nmos6502);
+        cpu.clock(&mut bus).unwrap();
+        assert_eq!(cpu.accumulator(), 0xD5);
+        assert_eq!(cpu.variant(), Variant::Nmos6502);
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(cpu.variant(), Variant::Ricoh2A03);
+    }
+
+    #[test]
+    fn unmapped_read_stops_the_instruction() {
+        // LDA $9000
+        let mut bus = PartialBus::with_program(&[0xAD, 0x00, 0x90]);
+        let mut cpu = Cpu6502::new();
+        cpu.program_counter = 0x0200;
+        assert_eq!(cpu.step_instruction(&mut bus).unwrap_err(), EmulationError::UnmappedRead { address: 0x9000 });
+        assert!(cpu.step_instruction(&mut bus).is_ok());
+    }
+
+    #[test]
+    fn rejected_write_is_a_bus_fault() {
+        // STA $9000
+        let mut bus = PartialBus::with_program(&[0x8D, 0x00, 0x90]);
+        let mut cpu = Cpu6502::new();
+        cpu.program_counter = 0x0200;
+        assert_eq!(cpu.step_instruction(&mut bus).unwrap_err(), EmulationError::BusFault { address: 0x9000 });
+    }
+
+    #[test]
+    fn jam_is_reported_as_an_illegal_opcode() {
+        let mut bus = TestBus::with_program(&[0x02]);
+        let mut cpu = cpu_at_program();
+        cpu.set_jam_policy(JamPolicy::Report);
+        assert_eq!(
+            cpu.step_instruction(&mut bus).unwrap_err(),
+            EmulationError::IllegalOpcode { program_counter: 0x0200, opcode: 0x02 }
+        );
+        assert!(cpu.is_jammed());
+    }
+
+    #[test]
+    fn jam_halts_until_reset() {
+        let mut bus = TestBus::with_program(&[0x02]);
+        let mut cpu = cpu_at_program();
+        cpu.step_instruction(&mut bus).unwrap();
+        assert!(cpu.is_jammed());
+        cpu.step_instruction(&mut bus).unwrap();
+        assert!(cpu.is_jammed());
+        cpu.reset();
+        cpu.step_instruction(&mut bus).unwrap();
+        assert!(!cpu.is_jammed());
+        assert_eq!(cpu.program_counter, 0x8000);
+    }
+}
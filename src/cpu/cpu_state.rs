@@ -0,0 +1,13 @@
+use crate::cpu::processor_status_register::ProcessorStatusRegister;
+
+/// A copy of the programmer-visible registers of a `Cpu6502`, for comparing or restoring CPU
+/// state between instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CpuState {
+    pub accumulator: u8,
+    pub x_register: u8,
+    pub y_register: u8,
+    pub stack_pointer: u8,
+    pub program_counter: u16,
+    pub status_register: ProcessorStatusRegister,
+}
@@ -0,0 +1,143 @@
+use crate::cpu::addressing_mode::AddressingMode;
+use crate::cpu::instruction::to_instruction;
+use crate::cpu::variant::Variant;
+use crate::traits::read::Read;
+use std::fmt;
+
+/// A single decoded instruction, rendered as e.g. `C012  B1 20     LDA ($20),Y`. Undocumented
+/// opcodes are flagged with a `*` in front of the mnemonic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisassembledInstruction {
+    pub address: u16,
+    pub bytes: Vec<u8>,
+    pub mnemonic: String,
+    pub operand: String,
+    pub official: bool,
+}
+
+impl fmt::Display for DisassembledInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.bytes.iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let marker = if self.official { ' ' } else { '*' };
+        write!(f, "{:04X}  {:<8} {}{}", self.address, bytes, marker, self.mnemonic)?;
+        if !self.operand.is_empty() {
+            write!(f, " {}", self.operand)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes the instruction starting at the beginning of `bytes`, which is located at `address`,
+/// using the instruction set of `variant`. If `bytes` ends before the operand does, the
+/// available bytes are emitted as `.byte` data; an empty `bytes` gives a `.byte` with no data.
+pub fn disassemble_instruction(bytes: &[u8], address: u16, variant: Variant) -> DisassembledInstruction {
+    let instruction = bytes.first().map(|&opcode| to_instruction(opcode, variant));
+    let length = instruction.as_ref().map_or(1, |instruction| instruction.length as usize);
+
+    let Some(instruction) = instruction.filter(|_| bytes.len() >= length) else {
+        let data = bytes.iter()
+            .map(|byte| format!("${:02X}", byte))
+            .collect::<Vec<_>>()
+            .join(",");
+        return DisassembledInstruction {
+            address,
+            bytes: bytes.to_vec(),
+            mnemonic: ".byte".to_string(),
+            operand: data,
+            official: true,
+        };
+    };
+
+    DisassembledInstruction {
+        address,
+        bytes: bytes[..length].to_vec(),
+        mnemonic: instruction.mnemonic.to_string(),
+        operand: operand(&instruction.addressing_mode, address, &bytes[1..length]),
+        official: instruction.official,
+    }
+}
+
+/// Disassembles a whole program image loaded at `origin`.
+pub fn disassemble(bytes: &[u8], origin: u16, variant: Variant) -> Vec<DisassembledInstruction> {
+    let mut instructions = Vec::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let instruction = disassemble_instruction(&bytes[offset..], origin.wrapping_add(offset as u16), variant);
+        offset += instruction.bytes.len();
+        instructions.push(instruction);
+    }
+    instructions
+}
+
+/// Disassembles the instructions starting between `start` and `end` inclusive, inspecting
+/// memory with `read_only` so that device registers are left untouched. Unmapped bytes read
+/// as zero.
+pub fn disassemble_range<R: Read<u16, u8> + ?Sized>(memory: &R, start: u16, end: u16, variant: Variant) -> Vec<DisassembledInstruction> {
+    let mut instructions = Vec::new();
+    let mut address = start as u32;
+    while address <= end as u32 {
+        let bytes = (0..3)
+            .map(|offset| memory.read_only((address as u16).wrapping_add(offset)).unwrap_or(0))
+            .collect::<Vec<_>>();
+        let instruction = disassemble_instruction(&bytes, address as u16, variant);
+        address += instruction.bytes.len() as u32;
+        instructions.push(instruction);
+    }
+    instructions
+}
+
+/// Operand in standard assembler syntax. Branch offsets are shown as their target address.
+pub(crate) fn operand(addressing_mode: &AddressingMode, address: u16, operand: &[u8]) -> String {
+    let byte = || operand[0];
+    let word = || ((operand[1] as u16) << 8) | operand[0] as u16;
+    match addressing_mode {
+        AddressingMode::Implied => String::new(),
+        AddressingMode::Accumulator => "A".to_string(),
+        AddressingMode::Immediate => format!("#${:02X}", byte()),
+        AddressingMode::ZeroPage => format!("${:02X}", byte()),
+        AddressingMode::ZeroPageX => format!("${:02X},X", byte()),
+        AddressingMode::ZeroPageY => format!("${:02X},Y", byte()),
+        AddressingMode::Absolute => format!("${:04X}", word()),
+        AddressingMode::AbsoluteX => format!("${:04X},X", word()),
+        AddressingMode::AbsoluteY => format!("${:04X},Y", word()),
+        AddressingMode::Relative => {
+            format!("${:04X}", address.wrapping_add(2).wrapping_add(byte() as i8 as u16))
+        },
+        AddressingMode::Indirect => format!("(${:04X})", word()),
+        AddressingMode::IndirectX => format!("(${:02X},X)", byte()),
+        AddressingMode::IndirectY => format!("(${:02X}),Y", byte()),
+        AddressingMode::ZeroPageIndirect => format!("(${:02X})", byte()),
+        AddressingMode::AbsoluteIndexedIndirect => format!("(${:04X},X)", word()),
+        AddressingMode::ZeroPageRelative => {
+            let target = address.wrapping_add(3).wrapping_add(operand[1] as i8 as u16);
+            format!("${:02X},${:04X}", byte(), target)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_input_is_an_empty_byte_directive() {
+        let instruction = disassemble_instruction(&[], 0x8000, Variant::Ricoh2A03);
+        assert_eq!(instruction.mnemonic, ".byte");
+        assert!(instruction.bytes.is_empty());
+        assert!(instruction.operand.is_empty());
+    }
+
+    #[test]
+    fn truncated_operand_at_end_of_buffer() {
+        // NOP, then LDA abs missing its high byte.
+        let instructions = disassemble(&[0xEA, 0xAD, 0x34], 0x8000, Variant::Ricoh2A03);
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(instructions[0].mnemonic, "NOP");
+        assert_eq!(instructions[1].address, 0x8001);
+        assert_eq!(instructions[1].bytes, vec![0xAD, 0x34]);
+        assert_eq!(instructions[1].to_string(), "8001  AD 34     .byte $AD,$34");
+    }
+}
@@ -0,0 +1,29 @@
+use std::error::Error;
+use std::fmt;
+
+/// A condition that stops the emulated machine from carrying on, returned instead of panicking
+/// so that a bad ROM cannot take down the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmulationError {
+    /// The CPU executed an opcode that locks up the processor, with the JAM policy set to
+    /// `JamPolicy::Report`.
+    IllegalOpcode { program_counter: u16, opcode: u8 },
+    /// The CPU read from an address no device responds to.
+    UnmappedRead { address: u16 },
+    /// A device was unable to complete a write.
+    BusFault { address: u16 },
+}
+
+impl fmt::Display for EmulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmulationError::IllegalOpcode { program_counter, opcode } => {
+                write!(f, "illegal opcode ${:02X} at ${:04X}", opcode, program_counter)
+            },
+            EmulationError::UnmappedRead { address } => write!(f, "read from unmapped address ${:04X}", address),
+            EmulationError::BusFault { address } => write!(f, "bus fault writing ${:04X}", address),
+        }
+    }
+}
+
+impl Error for EmulationError {}
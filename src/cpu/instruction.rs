@@ -0,0 +1,687 @@
+use crate::cpu::opcode::Opcode;
+use crate::cpu::addressing_mode::AddressingMode;
+use crate::cpu::variant::Variant;
+
+const PAGE_CROSS: bool = true;
+const NO_PAGE_CROSS: bool = false;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub addressing_mode: AddressingMode,
+    /// Base cycle count, before any page-crossing or branch-taken penalty.
+    pub cycles: u8,
+    /// Size in bytes, opcode included.
+    pub length: u8,
+    /// Mnemonic as written by assemblers; the SKB/IGN multi-byte NOPs are all `NOP`, and the
+    /// 65C02 bit instructions carry their bit number, e.g. `SMB3`.
+    pub mnemonic: &'static str,
+    pub official: bool,
+    /// Whether crossing a page while indexing costs an extra cycle. For branches this is the
+    /// extra cycle taken when a taken branch lands on a different page.
+    pub page_cross_cycle: bool
+}
+
+impl Instruction {
+    const fn build_instruction(opcode: Opcode, addressing_mode: AddressingMode, cycles: u8, official: bool, page_cross_cycle: bool) -> Self {
+        Instruction {
+            opcode,
+            addressing_mode,
+            cycles,
+            length: addressing_mode.instruction_length(),
+            mnemonic: opcode.mnemonic(),
+            official,
+            page_cross_cycle
+        }
+    }
+
+    const fn official(opcode: Opcode, addressing_mode: AddressingMode, cycles: u8, page_cross_cycle: bool) -> Self {
+        Instruction::build_instruction(opcode, addressing_mode, cycles, true, page_cross_cycle)
+    }
+
+    const fn unofficial(opcode: Opcode, addressing_mode: AddressingMode, cycles: u8, page_cross_cycle: bool) -> Self {
+        Instruction::build_instruction(opcode, addressing_mode, cycles, false, page_cross_cycle)
+    }
+
+    /// RMB, SMB, BBR and BBS, which encode the bit they work on in the opcode.
+    const fn bit_operation(opcode: Opcode, bit: usize, addressing_mode: AddressingMode, cycles: u8, page_cross_cycle: bool) -> Self {
+        let mnemonics = match opcode {
+            Opcode::RMB => ["RMB0", "RMB1", "RMB2", "RMB3", "RMB4", "RMB5", "RMB6", "RMB7"],
+            Opcode::SMB => ["SMB0", "SMB1", "SMB2", "SMB3", "SMB4", "SMB5", "SMB6", "SMB7"],
+            Opcode::BBR => ["BBR0", "BBR1", "BBR2", "BBR3", "BBR4", "BBR5", "BBR6", "BBR7"],
+            _ => ["BBS0", "BBS1", "BBS2", "BBS3", "BBS4", "BBS5", "BBS6", "BBS7"],
+        };
+        Instruction {
+            mnemonic: mnemonics[bit],
+            ..Instruction::official(opcode, addressing_mode, cycles, page_cross_cycle)
+        }
+    }
+}
+
+/// Decodes an opcode byte for the given CPU. Every one of the 256 values is defined. The 2A03
+/// and NMOS 6502 share an instruction set; they differ only in decimal mode.
+pub fn to_instruction(instruction_code: u8, variant: Variant) -> &'static Instruction {
+    match variant {
+        Variant::Ricoh2A03 | Variant::Nmos6502 => &INSTRUCTIONS[instruction_code as usize],
+        Variant::Wdc65C02 => &INSTRUCTIONS_65C02[instruction_code as usize],
+    }
+}
+
+pub static INSTRUCTIONS: [Instruction; 256] = [
+    /* 0x00 */ Instruction::official(Opcode::BRK, AddressingMode::Implied, 7, NO_PAGE_CROSS),
+    /* 0x01 */ Instruction::official(Opcode::ORA, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0x02 */ Instruction::unofficial(Opcode::JAM, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x03 */ Instruction::unofficial(Opcode::SLO, AddressingMode::IndirectX, 8, NO_PAGE_CROSS),
+    /* 0x04 */ Instruction::unofficial(Opcode::IGN, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x05 */ Instruction::official(Opcode::ORA, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x06 */ Instruction::official(Opcode::ASL, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x07 */ Instruction::unofficial(Opcode::SLO, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x08 */ Instruction::official(Opcode::PHP, AddressingMode::Implied, 3, NO_PAGE_CROSS),
+    /* 0x09 */ Instruction::official(Opcode::ORA, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x0a */ Instruction::official(Opcode::ASL, AddressingMode::Accumulator, 2, NO_PAGE_CROSS),
+    /* 0x0b */ Instruction::unofficial(Opcode::ANC, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x0c */ Instruction::unofficial(Opcode::IGN, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x0d */ Instruction::official(Opcode::ORA, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x0e */ Instruction::official(Opcode::ASL, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0x0f */ Instruction::unofficial(Opcode::SLO, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+
+    /* 0x10 */ Instruction::official(Opcode::BPL, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0x11 */ Instruction::official(Opcode::ORA, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0x12 */ Instruction::unofficial(Opcode::JAM, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x13 */ Instruction::unofficial(Opcode::SLO, AddressingMode::IndirectY, 8, NO_PAGE_CROSS),
+    /* 0x14 */ Instruction::unofficial(Opcode::IGN, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x15 */ Instruction::official(Opcode::ORA, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x16 */ Instruction::official(Opcode::ASL, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0x17 */ Instruction::unofficial(Opcode::SLO, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0x18 */ Instruction::official(Opcode::CLC, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x19 */ Instruction::official(Opcode::ORA, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0x1a */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x1b */ Instruction::unofficial(Opcode::SLO, AddressingMode::AbsoluteY, 7, NO_PAGE_CROSS),
+    /* 0x1c */ Instruction::unofficial(Opcode::IGN, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x1d */ Instruction::official(Opcode::ORA, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x1e */ Instruction::official(Opcode::ASL, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+    /* 0x1f */ Instruction::unofficial(Opcode::SLO, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+
+    /* 0x20 */ Instruction::official(Opcode::JSR, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0x21 */ Instruction::official(Opcode::AND, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0x22 */ Instruction::unofficial(Opcode::JAM, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x23 */ Instruction::unofficial(Opcode::RLA, AddressingMode::IndirectX, 8, NO_PAGE_CROSS),
+    /* 0x24 */ Instruction::official(Opcode::BIT, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x25 */ Instruction::official(Opcode::AND, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x26 */ Instruction::official(Opcode::ROL, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x27 */ Instruction::unofficial(Opcode::RLA, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x28 */ Instruction::official(Opcode::PLP, AddressingMode::Implied, 4, NO_PAGE_CROSS),
+    /* 0x29 */ Instruction::official(Opcode::AND, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x2a */ Instruction::official(Opcode::ROL, AddressingMode::Accumulator, 2, NO_PAGE_CROSS),
+    /* 0x2b */ Instruction::unofficial(Opcode::ANC, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x2c */ Instruction::official(Opcode::BIT, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x2d */ Instruction::official(Opcode::AND, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x2e */ Instruction::official(Opcode::ROL, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0x2f */ Instruction::unofficial(Opcode::RLA, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+
+    /* 0x30 */ Instruction::official(Opcode::BMI, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0x31 */ Instruction::official(Opcode::AND, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0x32 */ Instruction::unofficial(Opcode::JAM, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x33 */ Instruction::unofficial(Opcode::RLA, AddressingMode::IndirectY, 8, NO_PAGE_CROSS),
+    /* 0x34 */ Instruction::unofficial(Opcode::IGN, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x35 */ Instruction::official(Opcode::AND, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x36 */ Instruction::official(Opcode::ROL, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0x37 */ Instruction::unofficial(Opcode::RLA, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0x38 */ Instruction::official(Opcode::SEC, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x39 */ Instruction::official(Opcode::AND, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0x3a */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x3b */ Instruction::unofficial(Opcode::RLA, AddressingMode::AbsoluteY, 7, NO_PAGE_CROSS),
+    /* 0x3c */ Instruction::unofficial(Opcode::IGN, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x3d */ Instruction::official(Opcode::AND, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x3e */ Instruction::official(Opcode::ROL, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+    /* 0x3f */ Instruction::unofficial(Opcode::RLA, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+
+    /* 0x40 */ Instruction::official(Opcode::RTI, AddressingMode::Implied, 6, NO_PAGE_CROSS),
+    /* 0x41 */ Instruction::official(Opcode::EOR, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0x42 */ Instruction::unofficial(Opcode::JAM, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x43 */ Instruction::unofficial(Opcode::SRE, AddressingMode::IndirectX, 8, NO_PAGE_CROSS),
+    /* 0x44 */ Instruction::unofficial(Opcode::IGN, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x45 */ Instruction::official(Opcode::EOR, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x46 */ Instruction::official(Opcode::LSR, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x47 */ Instruction::unofficial(Opcode::SRE, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x48 */ Instruction::official(Opcode::PHA, AddressingMode::Implied, 3, NO_PAGE_CROSS),
+    /* 0x49 */ Instruction::official(Opcode::EOR, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x4a */ Instruction::official(Opcode::LSR, AddressingMode::Accumulator, 2, NO_PAGE_CROSS),
+    /* 0x4b */ Instruction::unofficial(Opcode::ALR, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x4c */ Instruction::official(Opcode::JMP, AddressingMode::Absolute, 3, NO_PAGE_CROSS),
+    /* 0x4d */ Instruction::official(Opcode::EOR, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x4e */ Instruction::official(Opcode::LSR, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0x4f */ Instruction::unofficial(Opcode::SRE, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+
+    /* 0x50 */ Instruction::official(Opcode::BVC, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0x51 */ Instruction::official(Opcode::EOR, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0x52 */ Instruction::unofficial(Opcode::JAM, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x53 */ Instruction::unofficial(Opcode::SRE, AddressingMode::IndirectY, 8, NO_PAGE_CROSS),
+    /* 0x54 */ Instruction::unofficial(Opcode::IGN, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x55 */ Instruction::official(Opcode::EOR, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x56 */ Instruction::official(Opcode::LSR, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0x57 */ Instruction::unofficial(Opcode::SRE, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0x58 */ Instruction::official(Opcode::CLI, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x59 */ Instruction::official(Opcode::EOR, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0x5a */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x5b */ Instruction::unofficial(Opcode::SRE, AddressingMode::AbsoluteY, 7, NO_PAGE_CROSS),
+    /* 0x5c */ Instruction::unofficial(Opcode::IGN, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x5d */ Instruction::official(Opcode::EOR, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x5e */ Instruction::official(Opcode::LSR, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+    /* 0x5f */ Instruction::unofficial(Opcode::SRE, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+
+    /* 0x60 */ Instruction::official(Opcode::RTS, AddressingMode::Implied, 6, NO_PAGE_CROSS),
+    /* 0x61 */ Instruction::official(Opcode::ADC, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0x62 */ Instruction::unofficial(Opcode::JAM, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x63 */ Instruction::unofficial(Opcode::RRA, AddressingMode::IndirectX, 8, NO_PAGE_CROSS),
+    /* 0x64 */ Instruction::unofficial(Opcode::IGN, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x65 */ Instruction::official(Opcode::ADC, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x66 */ Instruction::official(Opcode::ROR, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x67 */ Instruction::unofficial(Opcode::RRA, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x68 */ Instruction::official(Opcode::PLA, AddressingMode::Implied, 4, NO_PAGE_CROSS),
+    /* 0x69 */ Instruction::official(Opcode::ADC, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x6a */ Instruction::official(Opcode::ROR, AddressingMode::Accumulator, 2, NO_PAGE_CROSS),
+    /* 0x6b */ Instruction::unofficial(Opcode::ARR, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x6c */ Instruction::official(Opcode::JMP, AddressingMode::Indirect, 5, NO_PAGE_CROSS),
+    /* 0x6d */ Instruction::official(Opcode::ADC, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x6e */ Instruction::official(Opcode::ROR, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0x6f */ Instruction::unofficial(Opcode::RRA, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+
+    /* 0x70 */ Instruction::official(Opcode::BVS, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0x71 */ Instruction::official(Opcode::ADC, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0x72 */ Instruction::unofficial(Opcode::JAM, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x73 */ Instruction::unofficial(Opcode::RRA, AddressingMode::IndirectY, 8, NO_PAGE_CROSS),
+    /* 0x74 */ Instruction::unofficial(Opcode::IGN, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x75 */ Instruction::official(Opcode::ADC, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x76 */ Instruction::official(Opcode::ROR, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0x77 */ Instruction::unofficial(Opcode::RRA, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0x78 */ Instruction::official(Opcode::SEI, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x79 */ Instruction::official(Opcode::ADC, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0x7a */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x7b */ Instruction::unofficial(Opcode::RRA, AddressingMode::AbsoluteY, 7, NO_PAGE_CROSS),
+    /* 0x7c */ Instruction::unofficial(Opcode::IGN, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x7d */ Instruction::official(Opcode::ADC, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x7e */ Instruction::official(Opcode::ROR, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+    /* 0x7f */ Instruction::unofficial(Opcode::RRA, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+
+    /* 0x80 */ Instruction::unofficial(Opcode::SKB, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x81 */ Instruction::official(Opcode::STA, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0x82 */ Instruction::unofficial(Opcode::SKB, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x83 */ Instruction::unofficial(Opcode::SAX, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0x84 */ Instruction::official(Opcode::STY, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x85 */ Instruction::official(Opcode::STA, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x86 */ Instruction::official(Opcode::STX, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x87 */ Instruction::unofficial(Opcode::SAX, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x88 */ Instruction::official(Opcode::DEY, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x89 */ Instruction::unofficial(Opcode::SKB, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x8a */ Instruction::official(Opcode::TXA, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x8b */ Instruction::unofficial(Opcode::XAA, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x8c */ Instruction::official(Opcode::STY, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x8d */ Instruction::official(Opcode::STA, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x8e */ Instruction::official(Opcode::STX, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x8f */ Instruction::unofficial(Opcode::SAX, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+
+    /* 0x90 */ Instruction::official(Opcode::BCC, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0x91 */ Instruction::official(Opcode::STA, AddressingMode::IndirectY, 6, NO_PAGE_CROSS),
+    /* 0x92 */ Instruction::unofficial(Opcode::JAM, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x93 */ Instruction::unofficial(Opcode::SHA, AddressingMode::IndirectY, 6, NO_PAGE_CROSS),
+    /* 0x94 */ Instruction::official(Opcode::STY, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x95 */ Instruction::official(Opcode::STA, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x96 */ Instruction::official(Opcode::STX, AddressingMode::ZeroPageY, 4, NO_PAGE_CROSS),
+    /* 0x97 */ Instruction::unofficial(Opcode::SAX, AddressingMode::ZeroPageY, 4, NO_PAGE_CROSS),
+    /* 0x98 */ Instruction::official(Opcode::TYA, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x99 */ Instruction::official(Opcode::STA, AddressingMode::AbsoluteY, 5, NO_PAGE_CROSS),
+    /* 0x9a */ Instruction::official(Opcode::TXS, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x9b */ Instruction::unofficial(Opcode::TAS, AddressingMode::AbsoluteY, 5, NO_PAGE_CROSS),
+    /* 0x9c */ Instruction::unofficial(Opcode::SHY, AddressingMode::AbsoluteX, 5, NO_PAGE_CROSS),
+    /* 0x9d */ Instruction::official(Opcode::STA, AddressingMode::AbsoluteX, 5, NO_PAGE_CROSS),
+    /* 0x9e */ Instruction::unofficial(Opcode::SHX, AddressingMode::AbsoluteY, 5, NO_PAGE_CROSS),
+    /* 0x9f */ Instruction::unofficial(Opcode::SHA, AddressingMode::AbsoluteY, 5, NO_PAGE_CROSS),
+
+    /* 0xa0 */ Instruction::official(Opcode::LDY, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xa1 */ Instruction::official(Opcode::LDA, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0xa2 */ Instruction::official(Opcode::LDX, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xa3 */ Instruction::unofficial(Opcode::LAX, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0xa4 */ Instruction::official(Opcode::LDY, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xa5 */ Instruction::official(Opcode::LDA, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xa6 */ Instruction::official(Opcode::LDX, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xa7 */ Instruction::unofficial(Opcode::LAX, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xa8 */ Instruction::official(Opcode::TAY, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xa9 */ Instruction::official(Opcode::LDA, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xaa */ Instruction::official(Opcode::TAX, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xab */ Instruction::unofficial(Opcode::LXA, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xac */ Instruction::official(Opcode::LDY, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xad */ Instruction::official(Opcode::LDA, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xae */ Instruction::official(Opcode::LDX, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xaf */ Instruction::unofficial(Opcode::LAX, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+
+    /* 0xb0 */ Instruction::official(Opcode::BCS, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0xb1 */ Instruction::official(Opcode::LDA, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0xb2 */ Instruction::unofficial(Opcode::JAM, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xb3 */ Instruction::unofficial(Opcode::LAX, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0xb4 */ Instruction::official(Opcode::LDY, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0xb5 */ Instruction::official(Opcode::LDA, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0xb6 */ Instruction::official(Opcode::LDX, AddressingMode::ZeroPageY, 4, NO_PAGE_CROSS),
+    /* 0xb7 */ Instruction::unofficial(Opcode::LAX, AddressingMode::ZeroPageY, 4, NO_PAGE_CROSS),
+    /* 0xb8 */ Instruction::official(Opcode::CLV, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xb9 */ Instruction::official(Opcode::LDA, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0xba */ Instruction::official(Opcode::TSX, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xbb */ Instruction::unofficial(Opcode::LAS, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0xbc */ Instruction::official(Opcode::LDY, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0xbd */ Instruction::official(Opcode::LDA, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0xbe */ Instruction::official(Opcode::LDX, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0xbf */ Instruction::unofficial(Opcode::LAX, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+
+    /* 0xc0 */ Instruction::official(Opcode::CPY, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xc1 */ Instruction::official(Opcode::CMP, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0xc2 */ Instruction::unofficial(Opcode::SKB, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xc3 */ Instruction::unofficial(Opcode::DCP, AddressingMode::IndirectX, 8, NO_PAGE_CROSS),
+    /* 0xc4 */ Instruction::official(Opcode::CPY, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xc5 */ Instruction::official(Opcode::CMP, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xc6 */ Instruction::official(Opcode::DEC, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0xc7 */ Instruction::unofficial(Opcode::DCP, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0xc8 */ Instruction::official(Opcode::INY, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xc9 */ Instruction::official(Opcode::CMP, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xca */ Instruction::official(Opcode::DEX, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xcb */ Instruction::unofficial(Opcode::AXS, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xcc */ Instruction::official(Opcode::CPY, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xcd */ Instruction::official(Opcode::CMP, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xce */ Instruction::official(Opcode::DEC, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0xcf */ Instruction::unofficial(Opcode::DCP, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+
+    /* 0xd0 */ Instruction::official(Opcode::BNE, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0xd1 */ Instruction::official(Opcode::CMP, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0xd2 */ Instruction::unofficial(Opcode::JAM, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xd3 */ Instruction::unofficial(Opcode::DCP, AddressingMode::IndirectY, 8, NO_PAGE_CROSS),
+    /* 0xd4 */ Instruction::unofficial(Opcode::IGN, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0xd5 */ Instruction::official(Opcode::CMP, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0xd6 */ Instruction::official(Opcode::DEC, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0xd7 */ Instruction::unofficial(Opcode::DCP, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0xd8 */ Instruction::official(Opcode::CLD, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xd9 */ Instruction::official(Opcode::CMP, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0xda */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xdb */ Instruction::unofficial(Opcode::DCP, AddressingMode::AbsoluteY, 7, NO_PAGE_CROSS),
+    /* 0xdc */ Instruction::unofficial(Opcode::IGN, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0xdd */ Instruction::official(Opcode::CMP, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0xde */ Instruction::official(Opcode::DEC, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+    /* 0xdf */ Instruction::unofficial(Opcode::DCP, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+
+    /* 0xe0 */ Instruction::official(Opcode::CPX, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xe1 */ Instruction::official(Opcode::SBC, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0xe2 */ Instruction::unofficial(Opcode::SKB, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xe3 */ Instruction::unofficial(Opcode::ISC, AddressingMode::IndirectX, 8, NO_PAGE_CROSS),
+    /* 0xe4 */ Instruction::official(Opcode::CPX, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xe5 */ Instruction::official(Opcode::SBC, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xe6 */ Instruction::official(Opcode::INC, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0xe7 */ Instruction::unofficial(Opcode::ISC, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0xe8 */ Instruction::official(Opcode::INX, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xe9 */ Instruction::official(Opcode::SBC, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xea */ Instruction::official(Opcode::NOP, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xeb */ Instruction::unofficial(Opcode::SBC, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xec */ Instruction::official(Opcode::CPX, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xed */ Instruction::official(Opcode::SBC, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xee */ Instruction::official(Opcode::INC, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0xef */ Instruction::unofficial(Opcode::ISC, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+
+    /* 0xf0 */ Instruction::official(Opcode::BEQ, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0xf1 */ Instruction::official(Opcode::SBC, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0xf2 */ Instruction::unofficial(Opcode::JAM, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xf3 */ Instruction::unofficial(Opcode::ISC, AddressingMode::IndirectY, 8, NO_PAGE_CROSS),
+    /* 0xf4 */ Instruction::unofficial(Opcode::IGN, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0xf5 */ Instruction::official(Opcode::SBC, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0xf6 */ Instruction::official(Opcode::INC, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0xf7 */ Instruction::unofficial(Opcode::ISC, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0xf8 */ Instruction::official(Opcode::SED, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xf9 */ Instruction::official(Opcode::SBC, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0xfa */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xfb */ Instruction::unofficial(Opcode::ISC, AddressingMode::AbsoluteY, 7, NO_PAGE_CROSS),
+    /* 0xfc */ Instruction::unofficial(Opcode::IGN, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0xfd */ Instruction::official(Opcode::SBC, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0xfe */ Instruction::official(Opcode::INC, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+    /* 0xff */ Instruction::unofficial(Opcode::ISC, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+];
+
+/// The WDC 65C02 instruction set. The opcodes the NMOS parts leave undefined are NOPs of
+/// varying length and duration; only the single-byte ones finish in one cycle.
+pub static INSTRUCTIONS_65C02: [Instruction; 256] = [
+    /* 0x00 */ Instruction::official(Opcode::BRK, AddressingMode::Implied, 7, NO_PAGE_CROSS),
+    /* 0x01 */ Instruction::official(Opcode::ORA, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0x02 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x03 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x04 */ Instruction::official(Opcode::TSB, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x05 */ Instruction::official(Opcode::ORA, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x06 */ Instruction::official(Opcode::ASL, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x07 */ Instruction::bit_operation(Opcode::RMB, 0, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x08 */ Instruction::official(Opcode::PHP, AddressingMode::Implied, 3, NO_PAGE_CROSS),
+    /* 0x09 */ Instruction::official(Opcode::ORA, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x0a */ Instruction::official(Opcode::ASL, AddressingMode::Accumulator, 2, NO_PAGE_CROSS),
+    /* 0x0b */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x0c */ Instruction::official(Opcode::TSB, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0x0d */ Instruction::official(Opcode::ORA, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x0e */ Instruction::official(Opcode::ASL, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0x0f */ Instruction::bit_operation(Opcode::BBR, 0, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0x10 */ Instruction::official(Opcode::BPL, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0x11 */ Instruction::official(Opcode::ORA, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0x12 */ Instruction::official(Opcode::ORA, AddressingMode::ZeroPageIndirect, 5, NO_PAGE_CROSS),
+    /* 0x13 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x14 */ Instruction::official(Opcode::TRB, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x15 */ Instruction::official(Opcode::ORA, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x16 */ Instruction::official(Opcode::ASL, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0x17 */ Instruction::bit_operation(Opcode::RMB, 1, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x18 */ Instruction::official(Opcode::CLC, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x19 */ Instruction::official(Opcode::ORA, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0x1a */ Instruction::official(Opcode::INC, AddressingMode::Accumulator, 2, NO_PAGE_CROSS),
+    /* 0x1b */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x1c */ Instruction::official(Opcode::TRB, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0x1d */ Instruction::official(Opcode::ORA, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x1e */ Instruction::official(Opcode::ASL, AddressingMode::AbsoluteX, 6, PAGE_CROSS),
+    /* 0x1f */ Instruction::bit_operation(Opcode::BBR, 1, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0x20 */ Instruction::official(Opcode::JSR, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0x21 */ Instruction::official(Opcode::AND, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0x22 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x23 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x24 */ Instruction::official(Opcode::BIT, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x25 */ Instruction::official(Opcode::AND, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x26 */ Instruction::official(Opcode::ROL, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x27 */ Instruction::bit_operation(Opcode::RMB, 2, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x28 */ Instruction::official(Opcode::PLP, AddressingMode::Implied, 4, NO_PAGE_CROSS),
+    /* 0x29 */ Instruction::official(Opcode::AND, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x2a */ Instruction::official(Opcode::ROL, AddressingMode::Accumulator, 2, NO_PAGE_CROSS),
+    /* 0x2b */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x2c */ Instruction::official(Opcode::BIT, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x2d */ Instruction::official(Opcode::AND, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x2e */ Instruction::official(Opcode::ROL, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0x2f */ Instruction::bit_operation(Opcode::BBR, 2, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0x30 */ Instruction::official(Opcode::BMI, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0x31 */ Instruction::official(Opcode::AND, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0x32 */ Instruction::official(Opcode::AND, AddressingMode::ZeroPageIndirect, 5, NO_PAGE_CROSS),
+    /* 0x33 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x34 */ Instruction::official(Opcode::BIT, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x35 */ Instruction::official(Opcode::AND, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x36 */ Instruction::official(Opcode::ROL, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0x37 */ Instruction::bit_operation(Opcode::RMB, 3, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x38 */ Instruction::official(Opcode::SEC, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x39 */ Instruction::official(Opcode::AND, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0x3a */ Instruction::official(Opcode::DEC, AddressingMode::Accumulator, 2, NO_PAGE_CROSS),
+    /* 0x3b */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x3c */ Instruction::official(Opcode::BIT, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x3d */ Instruction::official(Opcode::AND, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x3e */ Instruction::official(Opcode::ROL, AddressingMode::AbsoluteX, 6, PAGE_CROSS),
+    /* 0x3f */ Instruction::bit_operation(Opcode::BBR, 3, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0x40 */ Instruction::official(Opcode::RTI, AddressingMode::Implied, 6, NO_PAGE_CROSS),
+    /* 0x41 */ Instruction::official(Opcode::EOR, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0x42 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x43 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x44 */ Instruction::unofficial(Opcode::NOP, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x45 */ Instruction::official(Opcode::EOR, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x46 */ Instruction::official(Opcode::LSR, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x47 */ Instruction::bit_operation(Opcode::RMB, 4, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x48 */ Instruction::official(Opcode::PHA, AddressingMode::Implied, 3, NO_PAGE_CROSS),
+    /* 0x49 */ Instruction::official(Opcode::EOR, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x4a */ Instruction::official(Opcode::LSR, AddressingMode::Accumulator, 2, NO_PAGE_CROSS),
+    /* 0x4b */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x4c */ Instruction::official(Opcode::JMP, AddressingMode::Absolute, 3, NO_PAGE_CROSS),
+    /* 0x4d */ Instruction::official(Opcode::EOR, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x4e */ Instruction::official(Opcode::LSR, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0x4f */ Instruction::bit_operation(Opcode::BBR, 4, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0x50 */ Instruction::official(Opcode::BVC, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0x51 */ Instruction::official(Opcode::EOR, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0x52 */ Instruction::official(Opcode::EOR, AddressingMode::ZeroPageIndirect, 5, NO_PAGE_CROSS),
+    /* 0x53 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x54 */ Instruction::unofficial(Opcode::NOP, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x55 */ Instruction::official(Opcode::EOR, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x56 */ Instruction::official(Opcode::LSR, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0x57 */ Instruction::bit_operation(Opcode::RMB, 5, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x58 */ Instruction::official(Opcode::CLI, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x59 */ Instruction::official(Opcode::EOR, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0x5a */ Instruction::official(Opcode::PHY, AddressingMode::Implied, 3, NO_PAGE_CROSS),
+    /* 0x5b */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x5c */ Instruction::unofficial(Opcode::NOP, AddressingMode::Absolute, 8, NO_PAGE_CROSS),
+    /* 0x5d */ Instruction::official(Opcode::EOR, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x5e */ Instruction::official(Opcode::LSR, AddressingMode::AbsoluteX, 6, PAGE_CROSS),
+    /* 0x5f */ Instruction::bit_operation(Opcode::BBR, 5, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0x60 */ Instruction::official(Opcode::RTS, AddressingMode::Implied, 6, NO_PAGE_CROSS),
+    /* 0x61 */ Instruction::official(Opcode::ADC, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0x62 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x63 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x64 */ Instruction::official(Opcode::STZ, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x65 */ Instruction::official(Opcode::ADC, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x66 */ Instruction::official(Opcode::ROR, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x67 */ Instruction::bit_operation(Opcode::RMB, 6, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x68 */ Instruction::official(Opcode::PLA, AddressingMode::Implied, 4, NO_PAGE_CROSS),
+    /* 0x69 */ Instruction::official(Opcode::ADC, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x6a */ Instruction::official(Opcode::ROR, AddressingMode::Accumulator, 2, NO_PAGE_CROSS),
+    /* 0x6b */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x6c */ Instruction::official(Opcode::JMP, AddressingMode::Indirect, 6, NO_PAGE_CROSS),
+    /* 0x6d */ Instruction::official(Opcode::ADC, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x6e */ Instruction::official(Opcode::ROR, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0x6f */ Instruction::bit_operation(Opcode::BBR, 6, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0x70 */ Instruction::official(Opcode::BVS, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0x71 */ Instruction::official(Opcode::ADC, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0x72 */ Instruction::official(Opcode::ADC, AddressingMode::ZeroPageIndirect, 5, NO_PAGE_CROSS),
+    /* 0x73 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x74 */ Instruction::official(Opcode::STZ, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x75 */ Instruction::official(Opcode::ADC, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x76 */ Instruction::official(Opcode::ROR, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0x77 */ Instruction::bit_operation(Opcode::RMB, 7, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x78 */ Instruction::official(Opcode::SEI, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x79 */ Instruction::official(Opcode::ADC, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0x7a */ Instruction::official(Opcode::PLY, AddressingMode::Implied, 4, NO_PAGE_CROSS),
+    /* 0x7b */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x7c */ Instruction::official(Opcode::JMP, AddressingMode::AbsoluteIndexedIndirect, 6, NO_PAGE_CROSS),
+    /* 0x7d */ Instruction::official(Opcode::ADC, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0x7e */ Instruction::official(Opcode::ROR, AddressingMode::AbsoluteX, 6, PAGE_CROSS),
+    /* 0x7f */ Instruction::bit_operation(Opcode::BBR, 7, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0x80 */ Instruction::official(Opcode::BRA, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0x81 */ Instruction::official(Opcode::STA, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0x82 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x83 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x84 */ Instruction::official(Opcode::STY, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x85 */ Instruction::official(Opcode::STA, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x86 */ Instruction::official(Opcode::STX, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0x87 */ Instruction::bit_operation(Opcode::SMB, 0, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x88 */ Instruction::official(Opcode::DEY, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x89 */ Instruction::official(Opcode::BIT, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0x8a */ Instruction::official(Opcode::TXA, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x8b */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x8c */ Instruction::official(Opcode::STY, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x8d */ Instruction::official(Opcode::STA, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x8e */ Instruction::official(Opcode::STX, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x8f */ Instruction::bit_operation(Opcode::BBS, 0, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0x90 */ Instruction::official(Opcode::BCC, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0x91 */ Instruction::official(Opcode::STA, AddressingMode::IndirectY, 6, NO_PAGE_CROSS),
+    /* 0x92 */ Instruction::official(Opcode::STA, AddressingMode::ZeroPageIndirect, 5, NO_PAGE_CROSS),
+    /* 0x93 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x94 */ Instruction::official(Opcode::STY, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x95 */ Instruction::official(Opcode::STA, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0x96 */ Instruction::official(Opcode::STX, AddressingMode::ZeroPageY, 4, NO_PAGE_CROSS),
+    /* 0x97 */ Instruction::bit_operation(Opcode::SMB, 1, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0x98 */ Instruction::official(Opcode::TYA, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x99 */ Instruction::official(Opcode::STA, AddressingMode::AbsoluteY, 5, NO_PAGE_CROSS),
+    /* 0x9a */ Instruction::official(Opcode::TXS, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0x9b */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0x9c */ Instruction::official(Opcode::STZ, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0x9d */ Instruction::official(Opcode::STA, AddressingMode::AbsoluteX, 5, NO_PAGE_CROSS),
+    /* 0x9e */ Instruction::official(Opcode::STZ, AddressingMode::AbsoluteX, 5, NO_PAGE_CROSS),
+    /* 0x9f */ Instruction::bit_operation(Opcode::BBS, 1, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0xa0 */ Instruction::official(Opcode::LDY, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xa1 */ Instruction::official(Opcode::LDA, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0xa2 */ Instruction::official(Opcode::LDX, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xa3 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0xa4 */ Instruction::official(Opcode::LDY, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xa5 */ Instruction::official(Opcode::LDA, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xa6 */ Instruction::official(Opcode::LDX, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xa7 */ Instruction::bit_operation(Opcode::SMB, 2, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0xa8 */ Instruction::official(Opcode::TAY, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xa9 */ Instruction::official(Opcode::LDA, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xaa */ Instruction::official(Opcode::TAX, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xab */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0xac */ Instruction::official(Opcode::LDY, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xad */ Instruction::official(Opcode::LDA, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xae */ Instruction::official(Opcode::LDX, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xaf */ Instruction::bit_operation(Opcode::BBS, 2, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0xb0 */ Instruction::official(Opcode::BCS, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0xb1 */ Instruction::official(Opcode::LDA, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0xb2 */ Instruction::official(Opcode::LDA, AddressingMode::ZeroPageIndirect, 5, NO_PAGE_CROSS),
+    /* 0xb3 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0xb4 */ Instruction::official(Opcode::LDY, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0xb5 */ Instruction::official(Opcode::LDA, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0xb6 */ Instruction::official(Opcode::LDX, AddressingMode::ZeroPageY, 4, NO_PAGE_CROSS),
+    /* 0xb7 */ Instruction::bit_operation(Opcode::SMB, 3, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0xb8 */ Instruction::official(Opcode::CLV, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xb9 */ Instruction::official(Opcode::LDA, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0xba */ Instruction::official(Opcode::TSX, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xbb */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0xbc */ Instruction::official(Opcode::LDY, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0xbd */ Instruction::official(Opcode::LDA, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0xbe */ Instruction::official(Opcode::LDX, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0xbf */ Instruction::bit_operation(Opcode::BBS, 3, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0xc0 */ Instruction::official(Opcode::CPY, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xc1 */ Instruction::official(Opcode::CMP, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0xc2 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xc3 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0xc4 */ Instruction::official(Opcode::CPY, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xc5 */ Instruction::official(Opcode::CMP, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xc6 */ Instruction::official(Opcode::DEC, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0xc7 */ Instruction::bit_operation(Opcode::SMB, 4, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0xc8 */ Instruction::official(Opcode::INY, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xc9 */ Instruction::official(Opcode::CMP, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xca */ Instruction::official(Opcode::DEX, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xcb */ Instruction::official(Opcode::WAI, AddressingMode::Implied, 3, NO_PAGE_CROSS),
+    /* 0xcc */ Instruction::official(Opcode::CPY, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xcd */ Instruction::official(Opcode::CMP, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xce */ Instruction::official(Opcode::DEC, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0xcf */ Instruction::bit_operation(Opcode::BBS, 4, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0xd0 */ Instruction::official(Opcode::BNE, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0xd1 */ Instruction::official(Opcode::CMP, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0xd2 */ Instruction::official(Opcode::CMP, AddressingMode::ZeroPageIndirect, 5, NO_PAGE_CROSS),
+    /* 0xd3 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0xd4 */ Instruction::unofficial(Opcode::NOP, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0xd5 */ Instruction::official(Opcode::CMP, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0xd6 */ Instruction::official(Opcode::DEC, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0xd7 */ Instruction::bit_operation(Opcode::SMB, 5, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0xd8 */ Instruction::official(Opcode::CLD, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xd9 */ Instruction::official(Opcode::CMP, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0xda */ Instruction::official(Opcode::PHX, AddressingMode::Implied, 3, NO_PAGE_CROSS),
+    /* 0xdb */ Instruction::official(Opcode::STP, AddressingMode::Implied, 3, NO_PAGE_CROSS),
+    /* 0xdc */ Instruction::unofficial(Opcode::NOP, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xdd */ Instruction::official(Opcode::CMP, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0xde */ Instruction::official(Opcode::DEC, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+    /* 0xdf */ Instruction::bit_operation(Opcode::BBS, 5, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0xe0 */ Instruction::official(Opcode::CPX, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xe1 */ Instruction::official(Opcode::SBC, AddressingMode::IndirectX, 6, NO_PAGE_CROSS),
+    /* 0xe2 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xe3 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0xe4 */ Instruction::official(Opcode::CPX, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xe5 */ Instruction::official(Opcode::SBC, AddressingMode::ZeroPage, 3, NO_PAGE_CROSS),
+    /* 0xe6 */ Instruction::official(Opcode::INC, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0xe7 */ Instruction::bit_operation(Opcode::SMB, 6, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0xe8 */ Instruction::official(Opcode::INX, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xe9 */ Instruction::official(Opcode::SBC, AddressingMode::Immediate, 2, NO_PAGE_CROSS),
+    /* 0xea */ Instruction::official(Opcode::NOP, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xeb */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0xec */ Instruction::official(Opcode::CPX, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xed */ Instruction::official(Opcode::SBC, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xee */ Instruction::official(Opcode::INC, AddressingMode::Absolute, 6, NO_PAGE_CROSS),
+    /* 0xef */ Instruction::bit_operation(Opcode::BBS, 6, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+
+    /* 0xf0 */ Instruction::official(Opcode::BEQ, AddressingMode::Relative, 2, PAGE_CROSS),
+    /* 0xf1 */ Instruction::official(Opcode::SBC, AddressingMode::IndirectY, 5, PAGE_CROSS),
+    /* 0xf2 */ Instruction::official(Opcode::SBC, AddressingMode::ZeroPageIndirect, 5, NO_PAGE_CROSS),
+    /* 0xf3 */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0xf4 */ Instruction::unofficial(Opcode::NOP, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0xf5 */ Instruction::official(Opcode::SBC, AddressingMode::ZeroPageX, 4, NO_PAGE_CROSS),
+    /* 0xf6 */ Instruction::official(Opcode::INC, AddressingMode::ZeroPageX, 6, NO_PAGE_CROSS),
+    /* 0xf7 */ Instruction::bit_operation(Opcode::SMB, 7, AddressingMode::ZeroPage, 5, NO_PAGE_CROSS),
+    /* 0xf8 */ Instruction::official(Opcode::SED, AddressingMode::Implied, 2, NO_PAGE_CROSS),
+    /* 0xf9 */ Instruction::official(Opcode::SBC, AddressingMode::AbsoluteY, 4, PAGE_CROSS),
+    /* 0xfa */ Instruction::official(Opcode::PLX, AddressingMode::Implied, 4, NO_PAGE_CROSS),
+    /* 0xfb */ Instruction::unofficial(Opcode::NOP, AddressingMode::Implied, 1, NO_PAGE_CROSS),
+    /* 0xfc */ Instruction::unofficial(Opcode::NOP, AddressingMode::Absolute, 4, NO_PAGE_CROSS),
+    /* 0xfd */ Instruction::official(Opcode::SBC, AddressingMode::AbsoluteX, 4, PAGE_CROSS),
+    /* 0xfe */ Instruction::official(Opcode::INC, AddressingMode::AbsoluteX, 7, NO_PAGE_CROSS),
+    /* 0xff */ Instruction::bit_operation(Opcode::BBS, 7, AddressingMode::ZeroPageRelative, 5, PAGE_CROSS),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Base cycle counts of the NMOS 6502 by opcode, as listed in the usual opcode matrix.
+    const CYCLES: [u8; 256] = [
+        7, 6, 2, 8, 3, 3, 5, 5, 3, 2, 2, 2, 4, 4, 6, 6,
+        2, 5, 2, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7,
+        6, 6, 2, 8, 3, 3, 5, 5, 4, 2, 2, 2, 4, 4, 6, 6,
+        2, 5, 2, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7,
+        6, 6, 2, 8, 3, 3, 5, 5, 3, 2, 2, 2, 3, 4, 6, 6,
+        2, 5, 2, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7,
+        6, 6, 2, 8, 3, 3, 5, 5, 4, 2, 2, 2, 5, 4, 6, 6,
+        2, 5, 2, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7,
+        2, 6, 2, 6, 3, 3, 3, 3, 2, 2, 2, 2, 4, 4, 4, 4,
+        2, 6, 2, 6, 4, 4, 4, 4, 2, 5, 2, 5, 5, 5, 5, 5,
+        2, 6, 2, 6, 3, 3, 3, 3, 2, 2, 2, 2, 4, 4, 4, 4,
+        2, 5, 2, 5, 4, 4, 4, 4, 2, 4, 2, 4, 4, 4, 4, 4,
+        2, 6, 2, 8, 3, 3, 5, 5, 2, 2, 2, 2, 4, 4, 6, 6,
+        2, 5, 2, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7,
+        2, 6, 2, 8, 3, 3, 5, 5, 2, 2, 2, 2, 4, 4, 6, 6,
+        2, 5, 2, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7,
+    ];
+
+    #[test]
+    fn lengths_follow_the_addressing_mode() {
+        for (code, instruction) in INSTRUCTIONS.iter().chain(INSTRUCTIONS_65C02.iter()).enumerate() {
+            assert_eq!(instruction.length, instruction.addressing_mode.instruction_length(), "opcode {:02X}", code % 256);
+        }
+        assert_eq!(to_instruction(0x20, Variant::Nmos6502).length, 3);
+        assert_eq!(to_instruction(0x6c, Variant::Nmos6502).length, 3);
+        assert_eq!(to_instruction(0x0a, Variant::Nmos6502).length, 1);
+        assert_eq!(to_instruction(0xd0, Variant::Nmos6502).length, 2);
+    }
+
+    #[test]
+    fn cycles_match_the_opcode_matrix() {
+        for (code, instruction) in INSTRUCTIONS.iter().enumerate() {
+            assert_eq!(instruction.cycles, CYCLES[code], "opcode {:02X}", code);
+        }
+    }
+
+    #[test]
+    fn official_opcodes_are_the_documented_151() {
+        assert_eq!(INSTRUCTIONS.iter().filter(|instruction| instruction.official).count(), 151);
+        assert!(!to_instruction(0xeb, Variant::Nmos6502).official);
+        assert_eq!(to_instruction(0xeb, Variant::Nmos6502).mnemonic, "SBC");
+        assert_eq!(to_instruction(0x04, Variant::Nmos6502).mnemonic, "NOP");
+    }
+
+    #[test]
+    fn only_reads_take_the_page_cross_cycle() {
+        assert!(to_instruction(0xbd, Variant::Nmos6502).page_cross_cycle);
+        assert!(to_instruction(0xb1, Variant::Nmos6502).page_cross_cycle);
+        assert!(!to_instruction(0x9d, Variant::Nmos6502).page_cross_cycle);
+        assert!(!to_instruction(0xfe, Variant::Nmos6502).page_cross_cycle);
+        assert!(to_instruction(0xd0, Variant::Nmos6502).page_cross_cycle);
+    }
+
+    #[test]
+    fn wdc_65c02_decodes_the_reserved_opcodes_as_nops() {
+        let instruction = to_instruction(0x5c, Variant::Wdc65C02);
+        assert_eq!(instruction.mnemonic, "NOP");
+        assert_eq!((instruction.length, instruction.cycles), (3, 8));
+        assert_eq!(to_instruction(0x02, Variant::Wdc65C02).length, 2);
+        assert_eq!(to_instruction(0x03, Variant::Wdc65C02).cycles, 1);
+    }
+}
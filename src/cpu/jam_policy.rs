@@ -0,0 +1,11 @@
+/// What the CPU does when it executes one of the NMOS opcodes that lock up the processor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JamPolicy {
+    /// Behave like the hardware: stop fetching instructions until the next reset.
+    #[default]
+    Halt,
+    /// Carry on as if the opcode were a one-byte, two-cycle NOP.
+    Nop,
+    /// Halt, and return `EmulationError::IllegalOpcode` from the clock call that executed it.
+    Report,
+}
@@ -1,4 +1,12 @@
 pub mod cpu_6502;
+pub mod cpu_state;
+pub mod disassembler;
+pub mod emulation_error;
+pub mod jam_policy;
+pub mod processor_status_register;
+pub mod step;
+pub mod trace;
+pub mod variant;
 mod opcode;
 mod addressing_mode;
 mod instruction;
\ No newline at end of file
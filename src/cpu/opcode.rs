@@ -1,6 +1,17 @@
+use crate::cpu::addressing_mode::AddressingMode;
 use crate::cpu::cpu_6502::Cpu6502;
+use crate::cpu::processor_status_register::ProcessorStatusRegister;
+use crate::cpu::variant::Variant;
 
-#[derive(PartialEq, Eq, Clone)]
+/// How an instruction uses the memory operand resolved by its addressing mode.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MemoryAccess {
+    Read,
+    Write,
+    ReadModifyWrite,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Opcode {
     // binary op
     ADC,
@@ -83,221 +94,893 @@ pub enum Opcode {
     SRE,
     SKB,
     IGN,
+    // unstable
+    SHA,
+    SHX,
+    SHY,
+    TAS,
+    LAS,
+    XAA,
+    LXA,
+    // halt
+    JAM,
+    // 65C02
+    BRA,
+    PHX,
+    PHY,
+    PLX,
+    PLY,
+    STZ,
+    TRB,
+    TSB,
+    RMB,
+    SMB,
+    BBR,
+    BBS,
+    WAI,
+    STP,
 }
 
 impl Opcode {
-    pub fn execute(&self, cpu: &mut Cpu6502) -> u8 {
+    pub const fn mnemonic(&self) -> &'static str {
+        match self {
+            Opcode::ADC => "ADC",
+            Opcode::SBC => "SBC",
+            Opcode::AND => "AND",
+            Opcode::EOR => "EOR",
+            Opcode::ORA => "ORA",
+            Opcode::ASL => "ASL",
+            Opcode::LSR => "LSR",
+            Opcode::ROL => "ROL",
+            Opcode::ROR => "ROR",
+            Opcode::INC => "INC",
+            Opcode::INX => "INX",
+            Opcode::INY => "INY",
+            Opcode::DEC => "DEC",
+            Opcode::DEX => "DEX",
+            Opcode::DEY => "DEY",
+            Opcode::LDA => "LDA",
+            Opcode::LDX => "LDX",
+            Opcode::LDY => "LDY",
+            Opcode::STA => "STA",
+            Opcode::STX => "STX",
+            Opcode::STY => "STY",
+            Opcode::SEC => "SEC",
+            Opcode::SED => "SED",
+            Opcode::SEI => "SEI",
+            Opcode::CLC => "CLC",
+            Opcode::CLD => "CLD",
+            Opcode::CLI => "CLI",
+            Opcode::CLV => "CLV",
+            Opcode::CMP => "CMP",
+            Opcode::CPX => "CPX",
+            Opcode::CPY => "CPY",
+            Opcode::JMP => "JMP",
+            Opcode::JSR => "JSR",
+            Opcode::RTI => "RTI",
+            Opcode::RTS => "RTS",
+            Opcode::BCC => "BCC",
+            Opcode::BCS => "BCS",
+            Opcode::BEQ => "BEQ",
+            Opcode::BMI => "BMI",
+            Opcode::BNE => "BNE",
+            Opcode::BPL => "BPL",
+            Opcode::BVC => "BVC",
+            Opcode::BVS => "BVS",
+            Opcode::PHA => "PHA",
+            Opcode::PHP => "PHP",
+            Opcode::PLA => "PLA",
+            Opcode::PLP => "PLP",
+            Opcode::TAX => "TAX",
+            Opcode::TAY => "TAY",
+            Opcode::TSX => "TSX",
+            Opcode::TXA => "TXA",
+            Opcode::TXS => "TXS",
+            Opcode::TYA => "TYA",
+            Opcode::BRK => "BRK",
+            Opcode::BIT => "BIT",
+            Opcode::NOP => "NOP",
+            Opcode::ALR => "ALR",
+            Opcode::ANC => "ANC",
+            Opcode::ARR => "ARR",
+            Opcode::AXS => "AXS",
+            Opcode::LAX => "LAX",
+            Opcode::SAX => "SAX",
+            Opcode::DCP => "DCP",
+            Opcode::ISC => "ISC",
+            Opcode::RLA => "RLA",
+            Opcode::RRA => "RRA",
+            Opcode::SLO => "SLO",
+            Opcode::SRE => "SRE",
+            Opcode::SKB => "NOP",
+            Opcode::IGN => "NOP",
+            Opcode::SHA => "SHA",
+            Opcode::SHX => "SHX",
+            Opcode::SHY => "SHY",
+            Opcode::TAS => "TAS",
+            Opcode::LAS => "LAS",
+            Opcode::XAA => "XAA",
+            Opcode::LXA => "LXA",
+            Opcode::JAM => "JAM",
+            Opcode::BRA => "BRA",
+            Opcode::PHX => "PHX",
+            Opcode::PHY => "PHY",
+            Opcode::PLX => "PLX",
+            Opcode::PLY => "PLY",
+            Opcode::STZ => "STZ",
+            Opcode::TRB => "TRB",
+            Opcode::TSB => "TSB",
+            Opcode::RMB => "RMB",
+            Opcode::SMB => "SMB",
+            Opcode::BBR => "BBR",
+            Opcode::BBS => "BBS",
+            Opcode::WAI => "WAI",
+            Opcode::STP => "STP",
+        }
+    }
+
+    pub const fn memory_access(&self) -> MemoryAccess {
+        match self {
+            Opcode::STA | Opcode::STX | Opcode::STY | Opcode::SAX | Opcode::SHA | Opcode::SHX
+            | Opcode::SHY | Opcode::TAS | Opcode::STZ => MemoryAccess::Write,
+            Opcode::ASL | Opcode::LSR | Opcode::ROL | Opcode::ROR | Opcode::INC | Opcode::DEC
+            | Opcode::SLO | Opcode::RLA | Opcode::SRE | Opcode::RRA | Opcode::DCP
+            | Opcode::ISC | Opcode::TRB | Opcode::TSB | Opcode::RMB
+            | Opcode::SMB => MemoryAccess::ReadModifyWrite,
+            _ => MemoryAccess::Read,
+        }
+    }
+
+    /// Whether a branch instruction's condition holds. BBR and BBS test the zero page byte
+    /// already read into `cpu.fetched`.
+    pub fn branch_taken(&self, cpu: &Cpu6502) -> bool {
+        match self {
+            Opcode::BCC => !cpu.get_flag(ProcessorStatusRegister::CARRY),
+            Opcode::BCS => cpu.get_flag(ProcessorStatusRegister::CARRY),
+            Opcode::BEQ => cpu.get_flag(ProcessorStatusRegister::ZERO),
+            Opcode::BMI => cpu.get_flag(ProcessorStatusRegister::NEGATIVE),
+            Opcode::BNE => !cpu.get_flag(ProcessorStatusRegister::ZERO),
+            Opcode::BPL => !cpu.get_flag(ProcessorStatusRegister::NEGATIVE),
+            Opcode::BVC => !cpu.get_flag(ProcessorStatusRegister::OVERFLOW),
+            Opcode::BVS => cpu.get_flag(ProcessorStatusRegister::OVERFLOW),
+            Opcode::BRA => true,
+            Opcode::BBR => cpu.fetched & bit_mask(cpu) == 0,
+            Opcode::BBS => cpu.fetched & bit_mask(cpu) != 0,
+            _ => false,
+        }
+    }
+
+    /// Performs the data operation of the instruction; all bus traffic is driven by the CPU's
+    /// cycle sequencer. `cpu.fetched` holds the operand read for the instruction, and on return
+    /// holds the value to be written for stores, read-modify-write instructions and pushes.
+    /// Shifts and rotates in accumulator mode see the accumulator in `cpu.fetched`.
+    pub fn execute(&self, cpu: &mut Cpu6502) {
         match self {
             Opcode::ADC => {
-                0
+                add(cpu, cpu.fetched);
             },
             Opcode::SBC => {
-                0
+                subtract(cpu, cpu.fetched);
             },
             Opcode::AND => {
-                0
+                cpu.accumulator &= cpu.fetched;
+                cpu.set_zero_and_negative(cpu.accumulator);
             },
             Opcode::EOR => {
-                0
+                cpu.accumulator ^= cpu.fetched;
+                cpu.set_zero_and_negative(cpu.accumulator);
             },
             Opcode::ORA => {
-                0
+                cpu.accumulator |= cpu.fetched;
+                cpu.set_zero_and_negative(cpu.accumulator);
             },
             Opcode::ASL => {
-                0
+                cpu.fetched = shift_left(cpu, cpu.fetched);
             },
             Opcode::LSR => {
-                0
+                cpu.fetched = shift_right(cpu, cpu.fetched);
             },
             Opcode::ROL => {
-                0
+                cpu.fetched = rotate_left(cpu, cpu.fetched);
             },
             Opcode::ROR => {
-                0
+                cpu.fetched = rotate_right(cpu, cpu.fetched);
             },
             Opcode::INC => {
-                0
+                cpu.fetched = cpu.fetched.wrapping_add(1);
+                cpu.set_zero_and_negative(cpu.fetched);
             },
             Opcode::INX => {
-                0
+                cpu.x_register = cpu.x_register.wrapping_add(1);
+                cpu.set_zero_and_negative(cpu.x_register);
             },
             Opcode::INY => {
-                0
+                cpu.y_register = cpu.y_register.wrapping_add(1);
+                cpu.set_zero_and_negative(cpu.y_register);
             },
             Opcode::DEC => {
-                0
+                cpu.fetched = cpu.fetched.wrapping_sub(1);
+                cpu.set_zero_and_negative(cpu.fetched);
             },
             Opcode::DEX => {
-                0
+                cpu.x_register = cpu.x_register.wrapping_sub(1);
+                cpu.set_zero_and_negative(cpu.x_register);
             },
             Opcode::DEY => {
-                0
+                cpu.y_register = cpu.y_register.wrapping_sub(1);
+                cpu.set_zero_and_negative(cpu.y_register);
             },
             Opcode::LDA => {
-                0
+                cpu.accumulator = cpu.fetched;
+                cpu.set_zero_and_negative(cpu.accumulator);
             },
             Opcode::LDX => {
-                0
+                cpu.x_register = cpu.fetched;
+                cpu.set_zero_and_negative(cpu.x_register);
             },
             Opcode::LDY => {
-                0
+                cpu.y_register = cpu.fetched;
+                cpu.set_zero_and_negative(cpu.y_register);
             },
             Opcode::STA => {
-                0
+                cpu.fetched = cpu.accumulator;
             },
             Opcode::STX => {
-                0
+                cpu.fetched = cpu.x_register;
             },
             Opcode::STY => {
-                0
+                cpu.fetched = cpu.y_register;
             },
             Opcode::SEC => {
-                0
+                cpu.set_flag(ProcessorStatusRegister::CARRY, true);
             },
             Opcode::SED => {
-                0
+                cpu.set_flag(ProcessorStatusRegister::DECIMAL_MODE, true);
             },
             Opcode::SEI => {
-                0
+                cpu.set_flag(ProcessorStatusRegister::DISABLE_INTERRUPTS, true);
             },
             Opcode::CLC => {
-                0
+                cpu.set_flag(ProcessorStatusRegister::CARRY, false);
             },
             Opcode::CLD => {
-                0
+                cpu.set_flag(ProcessorStatusRegister::DECIMAL_MODE, false);
             },
             Opcode::CLI => {
-                0
+                cpu.set_flag(ProcessorStatusRegister::DISABLE_INTERRUPTS, false);
             },
             Opcode::CLV => {
-                0
+                cpu.set_flag(ProcessorStatusRegister::OVERFLOW, false);
             },
             Opcode::CMP => {
-                0
+                compare(cpu, cpu.accumulator, cpu.fetched);
             },
             Opcode::CPX => {
-                0
+                compare(cpu, cpu.x_register, cpu.fetched);
             },
             Opcode::CPY => {
-                0
-            },
-            Opcode::JMP => {
-                0
-            },
-            Opcode::JSR => {
-                0
-            },
-            Opcode::RTI => {
-                0
-            },
-            Opcode::RTS => {
-                0
-            },
-            Opcode::BCC => {
-                0
-            },
-            Opcode::BCS => {
-                0
-            },
-            Opcode::BEQ => {
-                0
-            },
-            Opcode::BMI => {
-                0
-            },
-            Opcode::BNE => {
-                0
-            },
-            Opcode::BPL => {
-                0
-            },
-            Opcode::BVC => {
-                0
-            },
-            Opcode::BVS => {
-                0
+                compare(cpu, cpu.y_register, cpu.fetched);
             },
+            // Control flow is carried out entirely by the cycle sequencer.
+            Opcode::JMP | Opcode::JSR | Opcode::RTI | Opcode::RTS | Opcode::BRK => {},
+            Opcode::BCC | Opcode::BCS | Opcode::BEQ | Opcode::BMI | Opcode::BNE | Opcode::BPL
+            | Opcode::BVC | Opcode::BVS | Opcode::BRA | Opcode::BBR | Opcode::BBS => {},
             Opcode::PHA => {
-                0
+                cpu.fetched = cpu.accumulator;
             },
             Opcode::PHP => {
-                0
+                cpu.fetched = cpu.status_register.to_stack(true);
             },
             Opcode::PLA => {
-                0
+                cpu.accumulator = cpu.fetched;
+                cpu.set_zero_and_negative(cpu.accumulator);
             },
             Opcode::PLP => {
-                0
+                cpu.status_register = ProcessorStatusRegister::from_stack(cpu.fetched);
             },
             Opcode::TAX => {
-                0
+                cpu.x_register = cpu.accumulator;
+                cpu.set_zero_and_negative(cpu.x_register);
             },
             Opcode::TAY => {
-                0
+                cpu.y_register = cpu.accumulator;
+                cpu.set_zero_and_negative(cpu.y_register);
             },
             Opcode::TSX => {
-                0
+                cpu.x_register = cpu.stack_pointer;
+                cpu.set_zero_and_negative(cpu.x_register);
             },
             Opcode::TXA => {
-                0
+                cpu.accumulator = cpu.x_register;
+                cpu.set_zero_and_negative(cpu.accumulator);
             },
             Opcode::TXS => {
-                0
+                cpu.stack_pointer = cpu.x_register;
             },
             Opcode::TYA => {
-                0
-            },
-            Opcode::BRK => {
-                0
+                cpu.accumulator = cpu.y_register;
+                cpu.set_zero_and_negative(cpu.accumulator);
             },
             Opcode::BIT => {
-                0
-            },
-            Opcode::NOP => {
-                0
-            },
+                cpu.set_flag(ProcessorStatusRegister::ZERO, cpu.accumulator & cpu.fetched == 0);
+                // The 65C02's BIT #imm only affects Z.
+                if cpu.instruction().addressing_mode != AddressingMode::Immediate {
+                    cpu.set_flag(ProcessorStatusRegister::OVERFLOW, cpu.fetched & 0x40 != 0);
+                    cpu.set_flag(ProcessorStatusRegister::NEGATIVE, cpu.fetched & 0x80 != 0);
+                }
+            },
+            // SKB and IGN still perform their operand read, which matters for side effects.
+            Opcode::NOP | Opcode::SKB | Opcode::IGN | Opcode::JAM => {},
             Opcode::ALR => {
-                0
+                let value = cpu.accumulator & cpu.fetched;
+                cpu.accumulator = shift_right(cpu, value);
             },
             Opcode::ANC => {
-                0
+                cpu.accumulator &= cpu.fetched;
+                cpu.set_zero_and_negative(cpu.accumulator);
+                cpu.set_flag(ProcessorStatusRegister::CARRY, cpu.accumulator & 0x80 != 0);
+            },
+            Opcode::ARR if cpu.variant() == Variant::Nmos6502 && cpu.decimal_mode() => {
+                nmos_decimal_arr(cpu, cpu.accumulator & cpu.fetched);
             },
             Opcode::ARR => {
-                0
+                let value = cpu.accumulator & cpu.fetched;
+                let carry_in = cpu.get_flag(ProcessorStatusRegister::CARRY) as u8;
+                cpu.accumulator = (value >> 1) | (carry_in << 7);
+                cpu.set_zero_and_negative(cpu.accumulator);
+                cpu.set_flag(ProcessorStatusRegister::CARRY, cpu.accumulator & 0x40 != 0);
+                cpu.set_flag(ProcessorStatusRegister::OVERFLOW, ((cpu.accumulator >> 6) ^ (cpu.accumulator >> 5)) & 0x01 != 0);
             },
             Opcode::AXS => {
-                0
+                let masked = cpu.accumulator & cpu.x_register;
+                cpu.set_flag(ProcessorStatusRegister::CARRY, masked >= cpu.fetched);
+                cpu.x_register = masked.wrapping_sub(cpu.fetched);
+                cpu.set_zero_and_negative(cpu.x_register);
             },
             Opcode::LAX => {
-                0
+                cpu.accumulator = cpu.fetched;
+                cpu.x_register = cpu.fetched;
+                cpu.set_zero_and_negative(cpu.accumulator);
             },
             Opcode::SAX => {
-                0
+                cpu.fetched = cpu.accumulator & cpu.x_register;
             },
             Opcode::DCP => {
-                0
+                cpu.fetched = cpu.fetched.wrapping_sub(1);
+                compare(cpu, cpu.accumulator, cpu.fetched);
             },
             Opcode::ISC => {
-                0
+                cpu.fetched = cpu.fetched.wrapping_add(1);
+                subtract(cpu, cpu.fetched);
             },
             Opcode::RLA => {
-                0
+                cpu.fetched = rotate_left(cpu, cpu.fetched);
+                cpu.accumulator &= cpu.fetched;
+                cpu.set_zero_and_negative(cpu.accumulator);
             },
             Opcode::RRA => {
-                0
+                cpu.fetched = rotate_right(cpu, cpu.fetched);
+                add(cpu, cpu.fetched);
             },
             Opcode::SLO => {
-                0
+                cpu.fetched = shift_left(cpu, cpu.fetched);
+                cpu.accumulator |= cpu.fetched;
+                cpu.set_zero_and_negative(cpu.accumulator);
             },
             Opcode::SRE => {
-                0
+                cpu.fetched = shift_right(cpu, cpu.fetched);
+                cpu.accumulator ^= cpu.fetched;
+                cpu.set_zero_and_negative(cpu.accumulator);
+            },
+            Opcode::SHA => {
+                let value = cpu.accumulator & cpu.x_register;
+                store_high_byte_and(cpu, value, cpu.y_register);
+            },
+            Opcode::SHX => {
+                store_high_byte_and(cpu, cpu.x_register, cpu.y_register);
+            },
+            Opcode::SHY => {
+                store_high_byte_and(cpu, cpu.y_register, cpu.x_register);
+            },
+            Opcode::TAS => {
+                cpu.stack_pointer = cpu.accumulator & cpu.x_register;
+                store_high_byte_and(cpu, cpu.stack_pointer, cpu.y_register);
+            },
+            Opcode::LAS => {
+                let value = cpu.fetched & cpu.stack_pointer;
+                cpu.accumulator = value;
+                cpu.x_register = value;
+                cpu.stack_pointer = value;
+                cpu.set_zero_and_negative(value);
+            },
+            Opcode::XAA => {
+                cpu.accumulator = (cpu.accumulator | UNSTABLE_MAGIC) & cpu.x_register & cpu.fetched;
+                cpu.set_zero_and_negative(cpu.accumulator);
             },
-            Opcode::SKB => {
-                0
+            Opcode::LXA => {
+                cpu.accumulator = (cpu.accumulator | UNSTABLE_MAGIC) & cpu.fetched;
+                cpu.x_register = cpu.accumulator;
+                cpu.set_zero_and_negative(cpu.accumulator);
             },
-            Opcode::IGN => {
-                0
+            Opcode::PHX => {
+                cpu.fetched = cpu.x_register;
             },
+            Opcode::PHY => {
+                cpu.fetched = cpu.y_register;
+            },
+            Opcode::PLX => {
+                cpu.x_register = cpu.fetched;
+                cpu.set_zero_and_negative(cpu.x_register);
+            },
+            Opcode::PLY => {
+                cpu.y_register = cpu.fetched;
+                cpu.set_zero_and_negative(cpu.y_register);
+            },
+            Opcode::STZ => {
+                cpu.fetched = 0;
+            },
+            Opcode::TRB => {
+                cpu.set_flag(ProcessorStatusRegister::ZERO, cpu.accumulator & cpu.fetched == 0);
+                cpu.fetched &= !cpu.accumulator;
+            },
+            Opcode::TSB => {
+                cpu.set_flag(ProcessorStatusRegister::ZERO, cpu.accumulator & cpu.fetched == 0);
+                cpu.fetched |= cpu.accumulator;
+            },
+            Opcode::RMB => {
+                cpu.fetched &= !bit_mask(cpu);
+            },
+            Opcode::SMB => {
+                cpu.fetched |= bit_mask(cpu);
+            },
+            Opcode::WAI => {
+                cpu.waiting = true;
+            },
+            // STP stops the clock until the next reset, which the CPU treats like a JAM.
+            Opcode::STP => {
+                cpu.jammed = true;
+            },
+        }
+    }
+}
+
+/// XAA and LXA mix the accumulator with a chip-dependent constant; $EE matches most NES consoles.
+const UNSTABLE_MAGIC: u8 = 0xEE;
+
+/// The bit tested or changed by RMB, SMB, BBR and BBS, encoded in bits 4-6 of the opcode.
+fn bit_mask(cpu: &Cpu6502) -> u8 {
+    1 << ((cpu.opcode >> 4) & 0x07)
+}
+
+fn add(cpu: &mut Cpu6502, value: u8) {
+    match cpu.variant() {
+        _ if !cpu.decimal_mode() => add_with_carry(cpu, value),
+        Variant::Wdc65C02 => cmos_decimal_add(cpu, value),
+        _ => nmos_decimal_add(cpu, value),
+    }
+}
+
+fn subtract(cpu: &mut Cpu6502, value: u8) {
+    match cpu.variant() {
+        _ if !cpu.decimal_mode() => add_with_carry(cpu, !value),
+        Variant::Wdc65C02 => cmos_decimal_subtract(cpu, value),
+        _ => nmos_decimal_subtract(cpu, value),
+    }
+}
+
+/// Binary add shared by ADC and SBC; SBC is an ADC of the one's complement of its operand.
+fn add_with_carry(cpu: &mut Cpu6502, value: u8) {
+    let carry_in = cpu.get_flag(ProcessorStatusRegister::CARRY) as u16;
+    let sum = cpu.accumulator as u16 + value as u16 + carry_in;
+    let result = sum as u8;
+    cpu.set_flag(ProcessorStatusRegister::CARRY, sum > 0xFF);
+    cpu.set_flag(ProcessorStatusRegister::OVERFLOW, (cpu.accumulator ^ result) & (value ^ result) & 0x80 != 0);
+    cpu.accumulator = result;
+    cpu.set_zero_and_negative(result);
+}
+
+/// NMOS decimal ADC. C holds the decimal carry, but Z reflects the binary sum and N and V are
+/// taken from the intermediate result before the high digit is adjusted.
+fn nmos_decimal_add(cpu: &mut Cpu6502, value: u8) {
+    let accumulator = cpu.accumulator as u16;
+    let operand = value as u16;
+    let carry_in = cpu.get_flag(ProcessorStatusRegister::CARRY) as u16;
+
+    let mut low = (accumulator & 0x0F) + (operand & 0x0F) + carry_in;
+    if low > 0x09 {
+        low += 0x06;
+    }
+    let mut high = (accumulator >> 4) + (operand >> 4) + (low > 0x0F) as u16;
+    let intermediate = ((high << 4) | (low & 0x0F)) as u8;
+
+    cpu.set_flag(ProcessorStatusRegister::ZERO, (accumulator + operand + carry_in) as u8 == 0);
+    cpu.set_flag(ProcessorStatusRegister::NEGATIVE, intermediate & 0x80 != 0);
+    cpu.set_flag(ProcessorStatusRegister::OVERFLOW, (cpu.accumulator ^ intermediate) & (value ^ intermediate) & 0x80 != 0);
+    if high > 0x09 {
+        high += 0x06;
+    }
+    cpu.set_flag(ProcessorStatusRegister::CARRY, high > 0x0F);
+    cpu.accumulator = ((high << 4) | (low & 0x0F)) as u8;
+}
+
+/// NMOS decimal SBC. Every flag is set exactly as for a binary subtraction; only the
+/// accumulator receives the decimal result.
+fn nmos_decimal_subtract(cpu: &mut Cpu6502, value: u8) {
+    let accumulator = cpu.accumulator as i16;
+    let operand = value as i16;
+    let borrow = !cpu.get_flag(ProcessorStatusRegister::CARRY) as i16;
+    add_with_carry(cpu, !value);
+
+    let mut low = (accumulator & 0x0F) - (operand & 0x0F) - borrow;
+    let mut high = (accumulator >> 4) - (operand >> 4);
+    if low < 0 {
+        low -= 0x06;
+        high -= 1;
+    }
+    if high < 0 {
+        high -= 0x06;
+    }
+    cpu.accumulator = ((high << 4) | (low & 0x0F)) as u8;
+}
+
+/// NMOS decimal ARR. N and Z come from the rotated value and V from bit 6 changing in the
+/// rotate, as in binary mode; then each digit of the AND result that is above 5 (counting its
+/// low bit twice) gets 6 added to the matching digit of the accumulator, and C is set if the
+/// high digit was adjusted.
+fn nmos_decimal_arr(cpu: &mut Cpu6502, value: u8) {
+    let carry_in = cpu.get_flag(ProcessorStatusRegister::CARRY) as u8;
+    let mut result = (value >> 1) | (carry_in << 7);
+    cpu.set_zero_and_negative(result);
+    cpu.set_flag(ProcessorStatusRegister::OVERFLOW, (value ^ result) & 0x40 != 0);
+
+    if (value & 0x0F) + (value & 0x01) > 0x05 {
+        result = (result & 0xF0) | (result.wrapping_add(0x06) & 0x0F);
+    }
+    let adjust_high = (value & 0xF0) as u16 + (value & 0x10) as u16 > 0x50;
+    if adjust_high {
+        result = result.wrapping_add(0x60);
+    }
+    cpu.set_flag(ProcessorStatusRegister::CARRY, adjust_high);
+    cpu.accumulator = result;
+}
+
+/// 65C02 decimal ADC, which sets N and Z from the decimal result.
+fn cmos_decimal_add(cpu: &mut Cpu6502, value: u8) {
+    let accumulator = cpu.accumulator as u16;
+    let operand = value as u16;
+    let carry_in = cpu.get_flag(ProcessorStatusRegister::CARRY) as u16;
+
+    let mut low = (accumulator & 0x0F) + (operand & 0x0F) + carry_in;
+    if low > 0x09 {
+        low = ((low + 0x06) & 0x0F) + 0x10;
+    }
+    let mut result = (accumulator & 0xF0) + (operand & 0xF0) + low;
+    cpu.set_flag(ProcessorStatusRegister::OVERFLOW, (accumulator ^ result) & (operand ^ result) & 0x80 != 0);
+    if result > 0x9F {
+        result += 0x60;
+    }
+    cpu.set_flag(ProcessorStatusRegister::CARRY, result > 0xFF);
+    cpu.accumulator = result as u8;
+    cpu.set_zero_and_negative(cpu.accumulator);
+}
+
+/// 65C02 decimal SBC. C and V are the binary results; N and Z follow the decimal result.
+fn cmos_decimal_subtract(cpu: &mut Cpu6502, value: u8) {
+    let accumulator = cpu.accumulator as i16;
+    let operand = value as i16;
+    let borrow = !cpu.get_flag(ProcessorStatusRegister::CARRY) as i16;
+    add_with_carry(cpu, !value);
+
+    let low = (accumulator & 0x0F) - (operand & 0x0F) - borrow;
+    let mut result = accumulator - operand - borrow;
+    if result < 0 {
+        result -= 0x60;
+    }
+    if low < 0 {
+        result -= 0x06;
+    }
+    cpu.accumulator = result as u8;
+    cpu.set_zero_and_negative(cpu.accumulator);
+}
+
+fn compare(cpu: &mut Cpu6502, register: u8, value: u8) {
+    cpu.set_flag(ProcessorStatusRegister::CARRY, register >= value);
+    cpu.set_zero_and_negative(register.wrapping_sub(value));
+}
+
+fn shift_left(cpu: &mut Cpu6502, value: u8) -> u8 {
+    cpu.set_flag(ProcessorStatusRegister::CARRY, value & 0x80 != 0);
+    let result = value << 1;
+    cpu.set_zero_and_negative(result);
+    result
+}
+
+fn shift_right(cpu: &mut Cpu6502, value: u8) -> u8 {
+    cpu.set_flag(ProcessorStatusRegister::CARRY, value & 0x01 != 0);
+    let result = value >> 1;
+    cpu.set_zero_and_negative(result);
+    result
+}
+
+fn rotate_left(cpu: &mut Cpu6502, value: u8) -> u8 {
+    let carry_in = cpu.get_flag(ProcessorStatusRegister::CARRY) as u8;
+    cpu.set_flag(ProcessorStatusRegister::CARRY, value & 0x80 != 0);
+    let result = (value << 1) | carry_in;
+    cpu.set_zero_and_negative(result);
+    result
+}
+
+fn rotate_right(cpu: &mut Cpu6502, value: u8) -> u8 {
+    let carry_in = cpu.get_flag(ProcessorStatusRegister::CARRY) as u8;
+    cpu.set_flag(ProcessorStatusRegister::CARRY, value & 0x01 != 0);
+    let result = (value >> 1) | (carry_in << 7);
+    cpu.set_zero_and_negative(result);
+    result
+}
+
+/// SHA, SHX, SHY and TAS store `value & (H + 1)`, where H is the high byte of the un-indexed
+/// base address. When indexing crosses a page the stored value also replaces the high byte of
+/// the target address.
+fn store_high_byte_and(cpu: &mut Cpu6502, value: u8, index: u8) {
+    let base = cpu.address_absolute.wrapping_sub(index as u16);
+    cpu.fetched = value & ((base >> 8) as u8).wrapping_add(1);
+    if base & 0xFF00 != cpu.address_absolute & 0xFF00 {
+        cpu.address_absolute = ((cpu.fetched as u16) << 8) | (cpu.address_absolute & 0x00FF);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::bus::Bus;
+    use crate::traits::read::Read;
+    use crate::traits::write::Write;
+
+    fn load(program: &[u8]) -> Bus {
+        let mut bus = Bus::new();
+        for (offset, byte) in program.iter().enumerate() {
+            bus.write(0x0200 + offset as u16, *byte);
         }
+        bus
+    }
+
+    /// Runs the instruction at $0200 and returns the number of cycles it took.
+    fn run(cpu: &mut Cpu6502, bus: &mut Bus) -> u64 {
+        cpu.program_counter = 0x0200;
+        cpu.step_instruction(bus).unwrap().cycles
+    }
+
+    #[test]
+    fn adc_sets_overflow_on_signed_overflow() {
+        let mut bus = load(&[0x69, 0x50]);
+        let mut cpu = Cpu6502::new();
+        cpu.accumulator = 0x50;
+        run(&mut cpu, &mut bus);
+        assert_eq!(cpu.accumulator, 0xA0);
+        assert!(cpu.get_flag(ProcessorStatusRegister::OVERFLOW));
+        assert!(cpu.get_flag(ProcessorStatusRegister::NEGATIVE));
+        assert!(!cpu.get_flag(ProcessorStatusRegister::CARRY));
+    }
+
+    #[test]
+    fn adc_carries_out_and_in() {
+        let mut bus = load(&[0x69, 0x01]);
+        let mut cpu = Cpu6502::new();
+        cpu.accumulator = 0xFF;
+        run(&mut cpu, &mut bus);
+        assert_eq!(cpu.accumulator, 0x00);
+        assert!(cpu.get_flag(ProcessorStatusRegister::CARRY));
+        assert!(cpu.get_flag(ProcessorStatusRegister::ZERO));
+        assert!(!cpu.get_flag(ProcessorStatusRegister::OVERFLOW));
+        run(&mut cpu, &mut bus);
+        assert_eq!(cpu.accumulator, 0x02);
+    }
+
+    #[test]
+    fn sbc_borrows_when_carry_is_clear() {
+        let mut bus = load(&[0xE9, 0x05]);
+        let mut cpu = Cpu6502::new();
+        cpu.accumulator = 0x10;
+        run(&mut cpu, &mut bus);
+        assert_eq!(cpu.accumulator, 0x0A);
+        assert!(cpu.get_flag(ProcessorStatusRegister::CARRY));
+
+        cpu.accumulator = 0x03;
+        run(&mut cpu, &mut bus);
+        assert_eq!(cpu.accumulator, 0xFE);
+        assert!(!cpu.get_flag(ProcessorStatusRegister::CARRY));
+        assert!(cpu.get_flag(ProcessorStatusRegister::NEGATIVE));
+    }
+
+    #[test]
+    fn compare_sets_carry_when_register_is_not_less() {
+        let mut bus = load(&[0xC9, 0x20]);
+        let mut cpu = Cpu6502::new();
+        cpu.accumulator = 0x10;
+        run(&mut cpu, &mut bus);
+        assert!(!cpu.get_flag(ProcessorStatusRegister::CARRY));
+        assert!(cpu.get_flag(ProcessorStatusRegister::NEGATIVE));
+        cpu.accumulator = 0x20;
+        run(&mut cpu, &mut bus);
+        assert!(cpu.get_flag(ProcessorStatusRegister::CARRY));
+        assert!(cpu.get_flag(ProcessorStatusRegister::ZERO));
+    }
+
+    #[test]
+    fn branch_costs_a_cycle_and_another_across_a_page() {
+        let mut bus = load(&[0xD0, 0x10]);
+        let mut cpu = Cpu6502::new();
+        cpu.set_flag(ProcessorStatusRegister::ZERO, true);
+        assert_eq!(run(&mut cpu, &mut bus), 2);
+        assert_eq!(cpu.program_counter, 0x0202);
+        cpu.set_flag(ProcessorStatusRegister::ZERO, false);
+        assert_eq!(run(&mut cpu, &mut bus), 3);
+        assert_eq!(cpu.program_counter, 0x0212);
+
+        let mut bus = load(&[0xD0, 0xF0]);
+        let mut cpu = Cpu6502::new();
+        assert_eq!(run(&mut cpu, &mut bus), 4);
+        assert_eq!(cpu.program_counter, 0x01F2);
+    }
+
+    #[test]
+    fn page_cross_penalty_only_for_reads() {
+        // LDA $12F0,X and STA $12F0,X with X = $20.
+        let mut bus = load(&[0xBD, 0xF0, 0x12]);
+        let mut cpu = Cpu6502::new();
+        cpu.x_register = 0x20;
+        assert_eq!(run(&mut cpu, &mut bus), 5);
+        cpu.x_register = 0x00;
+        assert_eq!(run(&mut cpu, &mut bus), 4);
+
+        let mut bus = load(&[0x9D, 0xF0, 0x12]);
+        let mut cpu = Cpu6502::new();
+        cpu.x_register = 0x20;
+        cpu.accumulator = 0x42;
+        assert_eq!(run(&mut cpu, &mut bus), 5);
+        assert_eq!(bus.read_only(0x1310), Some(0x42));
+    }
+
+    #[test]
+    fn jsr_and_rts_round_trip_through_the_stack() {
+        let mut bus = load(&[0x20, 0x00, 0x03]);
+        bus.write(0x0300, 0x60);
+        let mut cpu = Cpu6502::new();
+        cpu.stack_pointer = 0xFF;
+        run(&mut cpu, &mut bus);
+        assert_eq!(cpu.program_counter, 0x0300);
+        assert_eq!(cpu.stack_pointer, 0xFD);
+        assert_eq!(bus.read_only(0x01FF), Some(0x02));
+        assert_eq!(bus.read_only(0x01FE), Some(0x02));
+        assert_eq!(cpu.step_instruction(&mut bus).unwrap().cycles, 6);
+        assert_eq!(cpu.program_counter, 0x0203);
+        assert_eq!(cpu.stack_pointer, 0xFF);
+    }
+
+    #[test]
+    fn sax_stores_a_and_x_without_touching_flags() {
+        let mut bus = load(&[0x87, 0x10]);
+        let mut cpu = Cpu6502::new();
+        cpu.accumulator = 0xF0;
+        cpu.x_register = 0x3C;
+        cpu.set_flag(ProcessorStatusRegister::ZERO, true);
+        assert_eq!(run(&mut cpu, &mut bus), 3);
+        assert_eq!(bus.read_only(0x0010), Some(0x30));
+        assert!(cpu.get_flag(ProcessorStatusRegister::ZERO));
+    }
+
+    #[test]
+    fn lax_loads_a_and_x() {
+        let mut bus = load(&[0xA7, 0x10]);
+        bus.write(0x0010, 0x80);
+        let mut cpu = Cpu6502::new();
+        assert_eq!(run(&mut cpu, &mut bus), 3);
+        assert_eq!(cpu.accumulator, 0x80);
+        assert_eq!(cpu.x_register, 0x80);
+        assert!(cpu.get_flag(ProcessorStatusRegister::NEGATIVE));
+    }
+
+    #[test]
+    fn dcp_decrements_then_compares() {
+        let mut bus = load(&[0xC7, 0x10]);
+        bus.write(0x0010, 0x43);
+        let mut cpu = Cpu6502::new();
+        cpu.accumulator = 0x42;
+        assert_eq!(run(&mut cpu, &mut bus), 5);
+        assert_eq!(bus.read_only(0x0010), Some(0x42));
+        assert!(cpu.get_flag(ProcessorStatusRegister::ZERO));
+        assert!(cpu.get_flag(ProcessorStatusRegister::CARRY));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn jmp_indirect_does_not_carry_into_the_pointer_high_byte() {
+        let mut bus = load(&[0x6C, 0xFF, 0x03]);
+        bus.write(0x03FF, 0x34);
+        bus.write(0x0300, 0x12);
+        bus.write(0x0400, 0x56);
+        let mut cpu = Cpu6502::new();
+        assert_eq!(run(&mut cpu, &mut bus), 5);
+        assert_eq!(cpu.program_counter, 0x1234);
+    }
+
+    #[test]
+    fn wdc_65c02_jmp_indirect_carries_into_the_pointer_high_byte() {
+        let mut bus = load(&[0x6C, 0xFF, 0x03]);
+        bus.write(0x03FF, 0x34);
+        bus.write(0x0300, 0x12);
+        bus.write(0x0400, 0x56);
+        let mut cpu = Cpu6502::new();
+        cpu.set_variant(Variant::Wdc65C02);
+        assert_eq!(run(&mut cpu, &mut bus), 6);
+        assert_eq!(cpu.program_counter, 0x5634);
+    }
+
+    /// Runs `ADC #operand` with D set and returns the CPU afterwards.
+    fn decimal_adc(bus: &mut Bus, variant: Variant, accumulator: u8, operand: u8) -> Cpu6502 {
+        bus.write(0x0200, 0x69);
+        bus.write(0x0201, operand);
+        let mut cpu = Cpu6502::new();
+        cpu.set_variant(variant);
+        cpu.accumulator = accumulator;
+        cpu.set_flag(ProcessorStatusRegister::DECIMAL_MODE, true);
+        run(&mut cpu, bus);
+        cpu
+    }
+
+    #[test]
+    fn wdc_65c02_decimal_flags_follow_the_bcd_result() {
+        let mut bus = Bus::new();
+        let cpu = decimal_adc(&mut bus, Variant::Wdc65C02, 0x99, 0x01);
+        assert_eq!(cpu.accumulator, 0x00);
+        assert!(cpu.get_flag(ProcessorStatusRegister::CARRY));
+        assert!(cpu.get_flag(ProcessorStatusRegister::ZERO));
+        assert!(!cpu.get_flag(ProcessorStatusRegister::NEGATIVE));
+
+        let cpu = decimal_adc(&mut bus, Variant::Nmos6502, 0x99, 0x01);
+        assert_eq!(cpu.accumulator, 0x00);
+        assert!(cpu.get_flag(ProcessorStatusRegister::CARRY));
+        assert!(!cpu.get_flag(ProcessorStatusRegister::ZERO));
+    }
+
+    /// Runs `ARR #operand` with D set and returns the CPU afterwards.
+    fn decimal_arr(bus: &mut Bus, variant: Variant, accumulator: u8, operand: u8, carry: bool) -> Cpu6502 {
+        bus.write(0x0200, 0x6B);
+        bus.write(0x0201, operand);
+        let mut cpu = Cpu6502::new();
+        cpu.set_variant(variant);
+        cpu.accumulator = accumulator;
+        cpu.set_flag(ProcessorStatusRegister::CARRY, carry);
+        cpu.set_flag(ProcessorStatusRegister::DECIMAL_MODE, true);
+        run(&mut cpu, bus);
+        cpu
+    }
+
+    #[test]
+    fn nmos_decimal_arr_adjusts_both_digits() {
+        let mut bus = Bus::new();
+        let cpu = decimal_arr(&mut bus, Variant::Nmos6502, 0xFF, 0xFF, false);
+        assert_eq!(cpu.accumulator, 0xD5);
+        assert!(cpu.get_flag(ProcessorStatusRegister::CARRY));
+        assert!(!cpu.get_flag(ProcessorStatusRegister::OVERFLOW));
+        assert!(!cpu.get_flag(ProcessorStatusRegister::NEGATIVE));
+    }
+
+    #[test]
+    fn nmos_decimal_arr_without_adjustment() {
+        let mut bus = Bus::new();
+        let cpu = decimal_arr(&mut bus, Variant::Nmos6502, 0x44, 0x44, true);
+        assert_eq!(cpu.accumulator, 0xA2);
+        assert!(!cpu.get_flag(ProcessorStatusRegister::CARRY));
+        assert!(cpu.get_flag(ProcessorStatusRegister::OVERFLOW));
+        assert!(cpu.get_flag(ProcessorStatusRegister::NEGATIVE));
+    }
+
+    #[test]
+    fn ricoh_arr_ignores_decimal_flag() {
+        let mut bus = Bus::new();
+        let cpu = decimal_arr(&mut bus, Variant::Ricoh2A03, 0xFF, 0xFF, false);
+        assert_eq!(cpu.accumulator, 0x7F);
+        assert!(cpu.get_flag(ProcessorStatusRegister::CARRY));
+        assert!(!cpu.get_flag(ProcessorStatusRegister::OVERFLOW));
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt;
+use std::ops::BitOr;
+
+/// The 6502 processor status register (P), laid out as `NV-BDIZC` from bit 7 down to bit 0.
+///
+/// Bit 5 is not a real flag and always reads back as set. The B flag does not exist inside the
+/// CPU either; it only appears in copies of the register pushed to the stack, where it tells
+/// PHP/BRK (set) apart from IRQ/NMI (clear).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProcessorStatusRegister(u8);
+
+impl ProcessorStatusRegister {
+    pub const CARRY: ProcessorStatusRegister = ProcessorStatusRegister(0x01);
+    pub const ZERO: ProcessorStatusRegister = ProcessorStatusRegister(0x02);
+    pub const DISABLE_INTERRUPTS: ProcessorStatusRegister = ProcessorStatusRegister(0x04);
+    pub const DECIMAL_MODE: ProcessorStatusRegister = ProcessorStatusRegister(0x08);
+    pub const BREAK: ProcessorStatusRegister = ProcessorStatusRegister(0x10);
+    pub const UNUSED: ProcessorStatusRegister = ProcessorStatusRegister(0x20);
+    pub const OVERFLOW: ProcessorStatusRegister = ProcessorStatusRegister(0x40);
+    pub const NEGATIVE: ProcessorStatusRegister = ProcessorStatusRegister(0x80);
+
+    pub const fn from_bits(bits: u8) -> Self {
+        ProcessorStatusRegister(bits)
+    }
+
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub const fn contains(&self, flags: ProcessorStatusRegister) -> bool {
+        self.0 & flags.0 == flags.0
+    }
+
+    pub fn set(&mut self, flags: ProcessorStatusRegister, value: bool) {
+        if value {
+            self.0 |= flags.0;
+        } else {
+            self.0 &= !flags.0;
+        }
+    }
+
+    /// The byte pushed to the stack. PHP and BRK push with B set, IRQ and NMI with B clear;
+    /// bit 5 is always set.
+    pub const fn to_stack(&self, break_flag: bool) -> u8 {
+        let bits = self.0 | ProcessorStatusRegister::UNUSED.0;
+        if break_flag {
+            bits | ProcessorStatusRegister::BREAK.0
+        } else {
+            bits & !ProcessorStatusRegister::BREAK.0
+        }
+    }
+
+    /// The register as restored by PLP and RTI, which ignore bits 4 and 5 of the pulled byte.
+    pub const fn from_stack(bits: u8) -> Self {
+        ProcessorStatusRegister((bits & !ProcessorStatusRegister::BREAK.0) | ProcessorStatusRegister::UNUSED.0)
+    }
+}
+
+impl BitOr for ProcessorStatusRegister {
+    type Output = ProcessorStatusRegister;
+
+    fn bitor(self, rhs: ProcessorStatusRegister) -> ProcessorStatusRegister {
+        ProcessorStatusRegister(self.0 | rhs.0)
+    }
+}
+
+/// Formats the register as `NV-BDIZC`, showing a flag's letter when set and `.` when clear.
+impl fmt::Display for ProcessorStatusRegister {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (bit, letter) in "NV-BDIZC".chars().enumerate() {
+            let mask = 0x80 >> bit;
+            let symbol = if self.0 & mask != 0 { letter } else { '.' };
+            write!(f, "{}", symbol)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+/// What the CPU started on an instruction boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Executed {
+    Instruction { program_counter: u16, opcode: u8 },
+    NonMaskableInterrupt,
+    InterruptRequest,
+    Reset,
+}
+
+/// The outcome of `Cpu6502::step_instruction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub executed: Executed,
+    pub cycles: u64,
+}
@@ -0,0 +1,237 @@
+use crate::cpu::addressing_mode::AddressingMode;
+use crate::cpu::cpu_6502::Cpu6502;
+use crate::cpu::disassembler::disassemble_instruction;
+use crate::cpu::instruction::{to_instruction, Instruction};
+use crate::cpu::opcode::Opcode;
+use crate::cpu::variant::Variant;
+use crate::traits::cpu_bus::CpuBus;
+use std::fmt;
+use std::io;
+
+/// Writes one line per executed instruction in the format of the nestest.log golden log.
+///
+/// The CPU has no PPU to ask for its position, so the `PPU:` column assumes the PPU started
+/// aligned with the CPU and runs three dots per CPU cycle with rendering disabled.
+pub struct Tracer {
+    sink: Box<dyn io::Write + Send>,
+}
+
+impl Tracer {
+    /// The sink has to be `Send + 'static` because the tracer is owned by the CPU, and through it
+    /// by a `Console` that may be moved to another thread. To read the log back afterwards, write
+    /// to something shared such as a file or an `Arc<Mutex<Vec<u8>>>` behind a small adapter.
+    pub fn new<W: io::Write + Send + 'static>(sink: W) -> Self {
+        Tracer {
+            sink: Box::new(sink),
+        }
+    }
+
+    pub(crate) fn trace(&mut self, cpu: &Cpu6502, bus: &dyn CpuBus) -> io::Result<()> {
+        writeln!(self.sink, "{}", trace_line(cpu, bus))
+    }
+}
+
+impl fmt::Debug for Tracer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Tracer")
+    }
+}
+
+/// Formats the instruction at the program counter together with the current registers, e.g.
+/// `C000  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD PPU:  0, 21 CYC:7`.
+/// Memory is inspected with `read_only` so tracing does not disturb the emulation.
+pub fn trace_line(cpu: &Cpu6502, bus: &dyn CpuBus) -> String {
+    let program_counter = cpu.program_counter();
+    let instruction = to_instruction(peek(bus, program_counter), cpu.variant());
+    let bytes = (0..instruction.length)
+        .map(|offset| peek(bus, program_counter.wrapping_add(offset as u16)))
+        .collect::<Vec<_>>();
+    let disassembled = disassemble_instruction(&bytes, program_counter, cpu.variant());
+
+    let mut text = match instruction.opcode {
+        Opcode::ISC => "ISB".to_string(),
+        _ => disassembled.mnemonic.clone(),
+    };
+    if !disassembled.operand.is_empty() {
+        text = format!("{} {}{}", text, disassembled.operand, annotation(cpu, bus, instruction, &bytes));
+    }
+    let marker = if disassembled.official { ' ' } else { '*' };
+    let ppu_dots = cpu.total_cycles() * 3;
+
+    format!(
+        "{:04X}  {:<8} {}{:<32}A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} PPU:{:>3},{:>3} CYC:{}",
+        program_counter,
+        bytes.iter().map(|byte| format!("{:02X}", byte)).collect::<Vec<_>>().join(" "),
+        marker,
+        text,
+        cpu.accumulator(),
+        cpu.x_register(),
+        cpu.y_register(),
+        cpu.status().bits(),
+        cpu.stack_pointer(),
+        (ppu_dots / 341) % 262,
+        ppu_dots % 341,
+        cpu.total_cycles()
+    )
+}
+
+/// The effective address and memory contents nestest.log appends after the operand.
+fn annotation(cpu: &Cpu6502, bus: &dyn CpuBus, instruction: &Instruction, bytes: &[u8]) -> String {
+    let low = bytes.get(1).copied().unwrap_or(0);
+    let high = bytes.get(2).copied().unwrap_or(0);
+    let absolute = ((high as u16) << 8) | low as u16;
+
+    match instruction.addressing_mode {
+        AddressingMode::Implied | AddressingMode::Accumulator | AddressingMode::Immediate
+        | AddressingMode::Relative => String::new(),
+        AddressingMode::ZeroPage | AddressingMode::ZeroPageRelative => format!(" = {:02X}", peek(bus, low as u16)),
+        AddressingMode::ZeroPageX => {
+            let address = low.wrapping_add(cpu.x_register());
+            format!(" @ {:02X} = {:02X}", address, peek(bus, address as u16))
+        },
+        AddressingMode::ZeroPageY => {
+            let address = low.wrapping_add(cpu.y_register());
+            format!(" @ {:02X} = {:02X}", address, peek(bus, address as u16))
+        },
+        AddressingMode::Absolute => {
+            if instruction.opcode == Opcode::JMP || instruction.opcode == Opcode::JSR {
+                String::new()
+            } else {
+                format!(" = {:02X}", peek(bus, absolute))
+            }
+        },
+        AddressingMode::AbsoluteX => {
+            let address = absolute.wrapping_add(cpu.x_register() as u16);
+            format!(" @ {:04X} = {:02X}", address, peek(bus, address))
+        },
+        AddressingMode::AbsoluteY => {
+            let address = absolute.wrapping_add(cpu.y_register() as u16);
+            format!(" @ {:04X} = {:02X}", address, peek(bus, address))
+        },
+        AddressingMode::Indirect => {
+            let high_pointer = match cpu.variant() {
+                Variant::Wdc65C02 => absolute.wrapping_add(1),
+                _ => (absolute & 0xFF00) | (absolute.wrapping_add(1) & 0x00FF),
+            };
+            let target = ((peek(bus, high_pointer) as u16) << 8) | peek(bus, absolute) as u16;
+            format!(" = {:04X}", target)
+        },
+        AddressingMode::AbsoluteIndexedIndirect => {
+            let pointer = absolute.wrapping_add(cpu.x_register() as u16);
+            let target = ((peek(bus, pointer.wrapping_add(1)) as u16) << 8) | peek(bus, pointer) as u16;
+            format!(" @ {:04X} = {:04X}", pointer, target)
+        },
+        AddressingMode::IndirectX => {
+            let pointer = low.wrapping_add(cpu.x_register());
+            let address = peek_zero_page_word(bus, pointer);
+            format!(" @ {:02X} = {:04X} = {:02X}", pointer, address, peek(bus, address))
+        },
+        AddressingMode::IndirectY => {
+            let base = peek_zero_page_word(bus, low);
+            let address = base.wrapping_add(cpu.y_register() as u16);
+            format!(" = {:04X} @ {:04X} = {:02X}", base, address, peek(bus, address))
+        },
+        AddressingMode::ZeroPageIndirect => {
+            let address = peek_zero_page_word(bus, low);
+            format!(" = {:04X} = {:02X}", address, peek(bus, address))
+        },
+    }
+}
+
+fn peek(bus: &dyn CpuBus, address: u16) -> u8 {
+    bus.read_only(address).unwrap_or(0)
+}
+
+fn peek_zero_page_word(bus: &dyn CpuBus, pointer: u8) -> u16 {
+    let low = peek(bus, pointer as u16) as u16;
+    let high = peek(bus, pointer.wrapping_add(1) as u16) as u16;
+    (high << 8) | low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpu::cpu_6502::tests::TestBus;
+    use crate::cpu::processor_status_register::ProcessorStatusRegister;
+    use std::sync::{Arc, Mutex};
+
+    /// Lets the test read back what the tracer wrote.
+    #[derive(Clone, Default)]
+    struct SharedLog(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for SharedLog {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn cpu_at(program_counter: u16, registers: [u8; 5], total_cycles: u64) -> Cpu6502 {
+        let [accumulator, x_register, y_register, status, stack_pointer] = registers;
+        let mut cpu = Cpu6502::new();
+        cpu.set_program_counter(program_counter);
+        cpu.set_accumulator(accumulator);
+        cpu.set_x_register(x_register);
+        cpu.set_y_register(y_register);
+        cpu.set_status(ProcessorStatusRegister::from_bits(status));
+        cpu.set_stack_pointer(stack_pointer);
+        cpu.set_total_cycles(total_cycles);
+        cpu
+    }
+
+    #[test]
+    fn matches_the_first_nestest_line() {
+        let mut bus = TestBus::with_program(&[]);
+        bus.load(0xC000, &[0x4C, 0xF5, 0xC5]);
+        let cpu = cpu_at(0xC000, [0x00, 0x00, 0x00, 0x24, 0xFD], 7);
+        assert_eq!(
+            trace_line(&cpu, &bus),
+            "C000  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD PPU:  0, 21 CYC:7"
+        );
+    }
+
+    #[test]
+    fn annotates_the_effective_address_and_value() {
+        let mut bus = TestBus::with_program(&[]);
+        bus.load(0x0082, &[0x00, 0x03]);
+        bus.load(0x0300, &[0x5A]);
+        bus.load(0xD959, &[0xA1, 0x80]);
+        let cpu = cpu_at(0xD959, [0x00, 0x02, 0x00, 0x27, 0xFB], 7106);
+        assert_eq!(
+            trace_line(&cpu, &bus),
+            "D959  A1 80     LDA ($80,X) @ 82 = 0300 = 5A    A:00 X:02 Y:00 P:27 SP:FB PPU: 62,176 CYC:7106"
+        );
+    }
+
+    #[test]
+    fn marks_unofficial_opcodes_with_a_star() {
+        let mut bus = TestBus::with_program(&[]);
+        bus.load(0x00A9, &[0x00]);
+        bus.load(0xC6BD, &[0x04, 0xA9]);
+        let cpu = cpu_at(0xC6BD, [0xAA, 0x97, 0x4E, 0xEF, 0xF5], 10407);
+        assert_eq!(
+            trace_line(&cpu, &bus),
+            "C6BD  04 A9    *NOP $A9 = 00                    A:AA X:97 Y:4E P:EF SP:F5 PPU: 91,190 CYC:10407"
+        );
+    }
+
+    #[test]
+    fn tracer_writes_a_line_before_each_instruction() {
+        let mut bus = TestBus::with_program(&[]);
+        bus.load(0xC000, &[0x4C, 0xF5, 0xC5, 0x00]);
+        bus.load(0xC5F5, &[0xA2, 0x00]);
+        let log = SharedLog::default();
+        let mut cpu = cpu_at(0xC000, [0x00, 0x00, 0x00, 0x24, 0xFD], 7);
+        cpu.set_tracer(Some(Tracer::new(log.clone())));
+        cpu.step_instruction(&mut bus).unwrap();
+        cpu.step_instruction(&mut bus).unwrap();
+        assert_eq!(
+            String::from_utf8(log.0.lock().unwrap().clone()).unwrap(),
+            "C000  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD PPU:  0, 21 CYC:7\n\
+             C5F5  A2 00     LDX #$00                        A:00 X:00 Y:00 P:24 SP:FD PPU:  0, 30 CYC:10\n"
+        );
+    }
+}
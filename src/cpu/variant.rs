@@ -0,0 +1,20 @@
+/// The members of the 6502 family the core can emulate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    /// The NES CPU: an NMOS 6502 with the decimal mode circuitry disconnected, so the D flag
+    /// can be set but ADC and SBC always work in binary.
+    #[default]
+    Ricoh2A03,
+    /// The original NMOS 6502, with BCD arithmetic and its undocumented opcodes.
+    Nmos6502,
+    /// The WDC 65C02. Adds BRA, PHX/PHY/PLX/PLY, STZ, TRB/TSB, the bit instructions and the
+    /// `($nn)` and `($nnnn,X)` modes, turns every undefined opcode into a NOP, fixes the
+    /// JMP ($xxFF) page wrap and spends an extra cycle on decimal ADC and SBC.
+    Wdc65C02,
+}
+
+impl Variant {
+    pub const fn has_decimal_mode(&self) -> bool {
+        !matches!(self, Variant::Ricoh2A03)
+    }
+}
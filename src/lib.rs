@@ -0,0 +1,9 @@
+// Opcodes keep their datasheet mnemonics, and each type lives in a module of the same name.
+#![allow(clippy::upper_case_acronyms, clippy::module_inception)]
+
+pub mod bus;
+pub mod cartridge;
+pub mod console;
+pub mod cpu;
+pub mod mapper;
+pub mod traits;
\ No newline at end of file
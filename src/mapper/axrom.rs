@@ -0,0 +1,98 @@
+use crate::cartridge::cartridge_memory::CartridgeMemory;
+use crate::cartridge::header::Header;
+use crate::cartridge::mirroring::Mirroring;
+use crate::traits::mapper::Mapper;
+
+/// Mapper 7: a switchable 32KB PRG-ROM bank at $8000-$FFFF and 8KB of CHR-RAM. Writing to
+/// $8000-$FFFF selects the bank with bits 0-2 and which kilobyte of nametable RAM all four
+/// nametables show with bit 4.
+///
+/// Only some boards (AMROM, and AOROM without its extra gate) have bus conflicts, and games
+/// written for ANROM rely on there being none, so they are only emulated for NES 2.0
+/// submapper 2.
+#[derive(Debug, Clone)]
+pub struct Axrom {
+    bank: u8,
+    bus_conflicts: bool,
+    mirroring: Mirroring
+}
+
+impl Axrom {
+    pub fn new(header: &Header) -> Self {
+        Axrom {
+            bank: 0,
+            bus_conflicts: header.submapper == 2,
+            mirroring: Mirroring::SingleScreenLower
+        }
+    }
+}
+
+impl Mapper for Axrom {
+    fn cpu_read_only(&self, memory: &CartridgeMemory, address: u16) -> Option<u8> {
+        match address {
+            0x8000..=0xFFFF => Some(memory.read_prg_rom(self.bank as usize, 0x8000, address - 0x8000)),
+            _ => None,
+        }
+    }
+
+    fn cpu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        if address < 0x8000 {
+            return;
+        }
+        let data = if self.bus_conflicts {
+            data & self.cpu_read_only(memory, address).unwrap_or(0xFF)
+        } else {
+            data
+        };
+        self.bank = data & 0x07;
+        self.mirroring = if data & 0x10 != 0 {
+            Mirroring::SingleScreenUpper
+        } else {
+            Mirroring::SingleScreenLower
+        };
+    }
+
+    fn ppu_read_only(&self, memory: &CartridgeMemory, address: u16) -> u8 {
+        memory.read_chr(0, 0x2000, address)
+    }
+
+    fn ppu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        memory.write_chr(0, 0x2000, address, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapper::test_board::{banks, board, rom_memory};
+
+    fn axrom(submapper: u8, prg_rom: Vec<u8>) -> (Axrom, CartridgeMemory) {
+        board(Axrom::new, 7, submapper, rom_memory(prg_rom, vec![0; 0x2000], true))
+    }
+
+    #[test]
+    fn selects_the_32kb_bank_and_nametable() {
+        let (mut mapper, mut memory) = axrom(0, banks(8, 0x8000));
+        assert_eq!(mapper.mirroring(), Mirroring::SingleScreenLower);
+        mapper.cpu_write(&mut memory, 0x8000, 0x15);
+        assert_eq!(mapper.cpu_read(&memory, 0x8000), Some(5));
+        assert_eq!(mapper.cpu_read(&memory, 0xFFFF), Some(5));
+        assert_eq!(mapper.mirroring(), Mirroring::SingleScreenUpper);
+        mapper.cpu_write(&mut memory, 0xFFFF, 0x05);
+        assert_eq!(mapper.mirroring(), Mirroring::SingleScreenLower);
+    }
+
+    #[test]
+    fn bus_conflicts_only_on_submapper_2() {
+        let (mut mapper, mut memory) = axrom(2, vec![0x13; 0x8000 * 8]);
+        mapper.cpu_write(&mut memory, 0x8000, 0x17);
+        assert_eq!(mapper.bank, 3);
+        let (mut mapper, mut memory) = axrom(0, vec![0x13; 0x8000 * 8]);
+        mapper.cpu_write(&mut memory, 0x8000, 0x17);
+        assert_eq!(mapper.bank, 7);
+    }
+}
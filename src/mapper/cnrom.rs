@@ -0,0 +1,93 @@
+use crate::cartridge::cartridge_memory::CartridgeMemory;
+use crate::cartridge::header::Header;
+use crate::cartridge::mirroring::Mirroring;
+use crate::traits::mapper::Mapper;
+
+/// Mapper 3: PRG-ROM fixed as on NROM, with a switchable 8KB CHR-ROM bank. Writing anywhere in
+/// $8000-$FFFF selects the bank.
+///
+/// Like UxROM the write conflicts with the ROM still driving the bus, so the register receives
+/// the AND of the two. NES 2.0 submapper 1 marks boards without the conflict.
+#[derive(Debug, Clone)]
+pub struct Cnrom {
+    chr_bank: u8,
+    bus_conflicts: bool,
+    mirroring: Mirroring
+}
+
+impl Cnrom {
+    pub fn new(header: &Header) -> Self {
+        Cnrom {
+            chr_bank: 0,
+            bus_conflicts: header.submapper != 1,
+            mirroring: header.mirroring
+        }
+    }
+}
+
+impl Mapper for Cnrom {
+    fn cpu_read_only(&self, memory: &CartridgeMemory, address: u16) -> Option<u8> {
+        match address {
+            0x6000..=0x7FFF => memory.read_prg_ram(address - 0x6000),
+            0x8000..=0xFFFF => Some(memory.read_prg_rom(0, 0x8000, address - 0x8000)),
+            _ => None,
+        }
+    }
+
+    fn cpu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        match address {
+            0x6000..=0x7FFF => memory.write_prg_ram(address - 0x6000, data),
+            0x8000..=0xFFFF => {
+                self.chr_bank = if self.bus_conflicts {
+                    data & self.cpu_read_only(memory, address).unwrap_or(0xFF)
+                } else {
+                    data
+                };
+            },
+            _ => {},
+        }
+    }
+
+    fn ppu_read_only(&self, memory: &CartridgeMemory, address: u16) -> u8 {
+        memory.read_chr(self.chr_bank as usize, 0x2000, address)
+    }
+
+    fn ppu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        memory.write_chr(self.chr_bank as usize, 0x2000, address, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapper::test_board::{banks, board, rom_memory};
+
+    /// 32KB of PRG-ROM that reads $03 at $8000 and $FF everywhere else, and eight CHR banks.
+    fn cnrom(submapper: u8) -> (Cnrom, CartridgeMemory) {
+        let mut prg_rom = vec![0xFF; 0x8000];
+        prg_rom[0] = 0x03;
+        board(Cnrom::new, 3, submapper, rom_memory(prg_rom, banks(8, 0x2000), false))
+    }
+
+    #[test]
+    fn selects_the_chr_bank() {
+        let (mut mapper, mut memory) = cnrom(1);
+        assert_eq!(mapper.ppu_read(&memory, 0x1FFF), 0);
+        mapper.cpu_write(&mut memory, 0x8000, 6);
+        assert_eq!(mapper.ppu_read(&memory, 0x0000), 6);
+        assert_eq!(mapper.cpu_read(&memory, 0x8000), Some(0x03));
+    }
+
+    #[test]
+    fn bus_conflict_ands_the_value_with_the_rom() {
+        let (mut mapper, mut memory) = cnrom(0);
+        mapper.cpu_write(&mut memory, 0x8000, 0x06);
+        assert_eq!(mapper.ppu_read(&memory, 0x0000), 2);
+        mapper.cpu_write(&mut memory, 0x8001, 0x06);
+        assert_eq!(mapper.ppu_read(&memory, 0x0000), 6);
+    }
+}
@@ -0,0 +1,305 @@
+use crate::cartridge::cartridge_memory::CartridgeMemory;
+use crate::cartridge::header::Header;
+use crate::cartridge::mirroring::Mirroring;
+use crate::traits::mapper::Mapper;
+
+/// The shift register is empty when only this marker bit is set. It reaches bit 0 as the
+/// fourth bit is shifted in, so the next write is the fifth.
+const SHIFT_EMPTY: u8 = 0x10;
+const PRG_RAM_BANK_SIZE: usize = 0x2000;
+
+/// Mapper 1: Nintendo's MMC1, used by the SxROM boards.
+///
+/// The CPU loads its registers a bit at a time: each write to $8000-$FFFF shifts bit 0 of the
+/// value into a 5-bit shift register, and the fifth write copies the result into the register
+/// selected by bits 13 and 14 of that write's address. Writing a value with bit 7 set empties
+/// the shift register and locks the last PRG bank at $C000. The MMC1 ignores a write on the
+/// cycle straight after another one, so of the two writes a read-modify-write instruction makes
+/// only the first counts.
+///
+/// Boards with more memory than the MMC1 can address borrow bits of the CHR bank registers,
+/// which are otherwise unused with 8KB of CHR-RAM: SUROM and SXROM select the 256KB half of
+/// their 512KB PRG-ROM with bit 4, SOROM selects its 8KB PRG-RAM bank with bit 3 and SXROM with
+/// bits 2-3. In 4KB CHR mode the register used is the one for the pattern table the PPU last
+/// read from.
+#[derive(Debug, Clone)]
+pub struct Mmc1 {
+    shift: u8,
+    control: u8,
+    chr_bank_0: u8,
+    chr_bank_1: u8,
+    prg_bank: u8,
+    cycle: u64,
+    previous_write_cycle: Option<u64>,
+    ppu_a12: bool
+}
+
+impl Mmc1 {
+    pub fn new(_header: &Header) -> Self {
+        Mmc1 {
+            shift: SHIFT_EMPTY,
+            control: 0x0C,
+            chr_bank_0: 0,
+            chr_bank_1: 0,
+            prg_bank: 0,
+            cycle: 0,
+            previous_write_cycle: None,
+            ppu_a12: false
+        }
+    }
+
+    fn write_register(&mut self, address: u16, data: u8) {
+        match address {
+            0x8000..=0x9FFF => self.control = data,
+            0xA000..=0xBFFF => self.chr_bank_0 = data,
+            0xC000..=0xDFFF => self.chr_bank_1 = data,
+            _ => self.prg_bank = data,
+        }
+    }
+
+    /// The CHR bank register whose upper bits the large boards use for PRG banking.
+    fn outer_bank_register(&self) -> u8 {
+        if self.control & 0x10 != 0 && self.ppu_a12 {
+            self.chr_bank_1
+        } else {
+            self.chr_bank_0
+        }
+    }
+
+    /// The 16KB PRG-ROM bank at `address`.
+    fn prg_rom_bank(&self, memory: &CartridgeMemory, address: u16) -> usize {
+        let outer = if memory.prg_rom().len() > 0x40000 {
+            (self.outer_bank_register() & 0x10) as usize
+        } else {
+            0
+        };
+        let bank = (self.prg_bank & 0x0F) as usize;
+        let upper_half = address >= 0xC000;
+        outer | match ((self.control >> 2) & 0x03, upper_half) {
+            (0, _) | (1, _) => (bank & 0x0E) | upper_half as usize,
+            (2, false) => 0,
+            (2, true) => bank,
+            (_, false) => bank,
+            (_, true) => 0x0F,
+        }
+    }
+
+    /// Offset into PRG-RAM of `address` in $6000-$7FFF.
+    fn prg_ram_offset(&self, memory: &CartridgeMemory, address: u16) -> u16 {
+        let bank = match memory.prg_ram().len() {
+            0x4000 => (self.outer_bank_register() >> 3) & 0x01,
+            0x8000 => (self.outer_bank_register() >> 2) & 0x03,
+            _ => 0,
+        };
+        (bank as usize * PRG_RAM_BANK_SIZE) as u16 + (address - 0x6000)
+    }
+
+    fn prg_ram_enabled(&self) -> bool {
+        self.prg_bank & 0x10 == 0
+    }
+
+    /// The 4KB CHR bank at `address`.
+    fn chr_bank(&self, address: u16) -> usize {
+        if self.control & 0x10 == 0 {
+            (self.chr_bank_0 & 0x1E) as usize | (address >= 0x1000) as usize
+        } else if address < 0x1000 {
+            self.chr_bank_0 as usize
+        } else {
+            self.chr_bank_1 as usize
+        }
+    }
+}
+
+impl Mapper for Mmc1 {
+    fn cpu_read_only(&self, memory: &CartridgeMemory, address: u16) -> Option<u8> {
+        match address {
+            0x6000..=0x7FFF if self.prg_ram_enabled() => {
+                memory.read_prg_ram(self.prg_ram_offset(memory, address))
+            },
+            0x8000..=0xFFFF => Some(memory.read_prg_rom(self.prg_rom_bank(memory, address), 0x4000, address)),
+            _ => None,
+        }
+    }
+
+    fn cpu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        match address {
+            0x6000..=0x7FFF if self.prg_ram_enabled() => {
+                let offset = self.prg_ram_offset(memory, address);
+                memory.write_prg_ram(offset, data);
+            },
+            0x8000..=0xFFFF => {
+                let consecutive = self.previous_write_cycle == Some(self.cycle.wrapping_sub(1));
+                self.previous_write_cycle = Some(self.cycle);
+                if consecutive {
+                    return;
+                }
+                if data & 0x80 != 0 {
+                    self.shift = SHIFT_EMPTY;
+                    self.control |= 0x0C;
+                    return;
+                }
+                let complete = self.shift & 0x01 != 0;
+                self.shift = (self.shift >> 1) | ((data & 0x01) << 4);
+                if complete {
+                    self.write_register(address, self.shift);
+                    self.shift = SHIFT_EMPTY;
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn ppu_read(&mut self, memory: &CartridgeMemory, address: u16) -> u8 {
+        self.ppu_a12 = address & 0x1000 != 0;
+        self.ppu_read_only(memory, address)
+    }
+
+    fn ppu_read_only(&self, memory: &CartridgeMemory, address: u16) -> u8 {
+        memory.read_chr(self.chr_bank(address), 0x1000, address)
+    }
+
+    fn ppu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        memory.write_chr(self.chr_bank(address), 0x1000, address, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        match self.control & 0x03 {
+            0 => Mirroring::SingleScreenLower,
+            1 => Mirroring::SingleScreenUpper,
+            2 => Mirroring::Vertical,
+            _ => Mirroring::Horizontal,
+        }
+    }
+
+    fn notify_cpu_cycle(&mut self) {
+        self.cycle = self.cycle.wrapping_add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapper::test_board::{banks, board};
+
+    fn mmc1(prg_rom_banks: usize, prg_ram_size: usize) -> (Mmc1, CartridgeMemory) {
+        let prg_rom = banks(prg_rom_banks, 0x4000);
+        let memory = CartridgeMemory::new(prg_rom, vec![0; prg_ram_size], banks(16, 0x1000), false);
+        board(Mmc1::new, 1, 0, memory)
+    }
+
+    /// One write through the serial port, followed by a cycle without one.
+    fn write(mapper: &mut Mmc1, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        mapper.cpu_write(memory, address, data);
+        mapper.notify_cpu_cycle();
+        mapper.notify_cpu_cycle();
+    }
+
+    /// Loads a register with the five writes the game would make.
+    fn load(mapper: &mut Mmc1, memory: &mut CartridgeMemory, address: u16, value: u8) {
+        for bit in 0..5 {
+            write(mapper, memory, address, value >> bit);
+        }
+    }
+
+    #[test]
+    fn register_loads_on_the_fifth_write() {
+        let (mut mapper, mut memory) = mmc1(16, 0);
+        for bit in 0..4 {
+            write(&mut mapper, &mut memory, 0xE000, 0x05 >> bit);
+            assert_eq!(mapper.cpu_read(&memory, 0x8000), Some(0));
+        }
+        // Only bit 0 counts, and the address of the last write picks the register.
+        write(&mut mapper, &mut memory, 0xFFFF, 0x7E);
+        assert_eq!(mapper.cpu_read(&memory, 0x8000), Some(5));
+    }
+
+    #[test]
+    fn bit_7_resets_the_shift_register_and_fixes_the_last_bank() {
+        let (mut mapper, mut memory) = mmc1(16, 0);
+        load(&mut mapper, &mut memory, 0x8000, 0x08);
+        assert_eq!(mapper.cpu_read(&memory, 0xC000), Some(0));
+        write(&mut mapper, &mut memory, 0xE000, 0x01);
+        write(&mut mapper, &mut memory, 0xE000, 0x01);
+        write(&mut mapper, &mut memory, 0x8000, 0x80);
+        assert_eq!(mapper.cpu_read(&memory, 0xC000), Some(15));
+        load(&mut mapper, &mut memory, 0xE000, 0x02);
+        assert_eq!(mapper.cpu_read(&memory, 0x8000), Some(2));
+    }
+
+    #[test]
+    fn write_on_the_next_cycle_is_ignored() {
+        let (mut mapper, mut memory) = mmc1(16, 0);
+        for bit in 0..5 {
+            // The dummy write of a read-modify-write instruction, then the real one.
+            mapper.cpu_write(&mut memory, 0xE000, 0x06 >> bit);
+            mapper.notify_cpu_cycle();
+            mapper.cpu_write(&mut memory, 0xE000, !(0x06 >> bit));
+            mapper.notify_cpu_cycle();
+            mapper.notify_cpu_cycle();
+        }
+        assert_eq!(mapper.cpu_read(&memory, 0x8000), Some(6));
+    }
+
+    #[test]
+    fn prg_modes() {
+        let (mut mapper, mut memory) = mmc1(16, 0);
+        load(&mut mapper, &mut memory, 0xE000, 0x05);
+        let slots = |mapper: &Mmc1, memory: &CartridgeMemory| {
+            (mapper.cpu_read_only(memory, 0x8000), mapper.cpu_read_only(memory, 0xC000))
+        };
+        assert_eq!(slots(&mapper, &memory), (Some(5), Some(15)));
+        load(&mut mapper, &mut memory, 0x8000, 0x08);
+        assert_eq!(slots(&mapper, &memory), (Some(0), Some(5)));
+        load(&mut mapper, &mut memory, 0x8000, 0x04);
+        assert_eq!(slots(&mapper, &memory), (Some(4), Some(5)));
+        load(&mut mapper, &mut memory, 0x8000, 0x00);
+        assert_eq!(slots(&mapper, &memory), (Some(4), Some(5)));
+    }
+
+    #[test]
+    fn chr_modes_and_mirroring() {
+        let (mut mapper, mut memory) = mmc1(16, 0);
+        load(&mut mapper, &mut memory, 0xA000, 0x05);
+        load(&mut mapper, &mut memory, 0xC000, 0x09);
+        assert_eq!((mapper.ppu_read(&memory, 0x0000), mapper.ppu_read(&memory, 0x1000)), (4, 5));
+        load(&mut mapper, &mut memory, 0x8000, 0x12);
+        assert_eq!((mapper.ppu_read(&memory, 0x0000), mapper.ppu_read(&memory, 0x1000)), (5, 9));
+        assert_eq!(mapper.mirroring(), Mirroring::Vertical);
+        load(&mut mapper, &mut memory, 0x8000, 0x11);
+        assert_eq!(mapper.mirroring(), Mirroring::SingleScreenUpper);
+    }
+
+    #[test]
+    fn prg_ram_enable() {
+        let (mut mapper, mut memory) = mmc1(16, 0x2000);
+        mapper.cpu_write(&mut memory, 0x6000, 0x42);
+        load(&mut mapper, &mut memory, 0xE000, 0x10);
+        assert_eq!(mapper.cpu_read(&memory, 0x6000), None);
+        mapper.cpu_write(&mut memory, 0x6000, 0x17);
+        load(&mut mapper, &mut memory, 0xE000, 0x00);
+        assert_eq!(mapper.cpu_read(&memory, 0x6000), Some(0x42));
+    }
+
+    #[test]
+    fn surom_outer_bank_selects_the_256kb_half() {
+        let (mut mapper, mut memory) = mmc1(32, 0x2000);
+        assert_eq!(mapper.cpu_read(&memory, 0xC000), Some(15));
+        load(&mut mapper, &mut memory, 0xA000, 0x10);
+        load(&mut mapper, &mut memory, 0xE000, 0x03);
+        assert_eq!(mapper.cpu_read(&memory, 0x8000), Some(19));
+        assert_eq!(mapper.cpu_read(&memory, 0xC000), Some(31));
+    }
+
+    #[test]
+    fn prg_ram_banks_on_sorom_and_sxrom() {
+        let (mut mapper, mut memory) = mmc1(16, 0x4000);
+        load(&mut mapper, &mut memory, 0xA000, 0x08);
+        mapper.cpu_write(&mut memory, 0x7FFF, 1);
+        assert_eq!(memory.prg_ram()[0x3FFF], 1);
+
+        let (mut mapper, mut memory) = mmc1(32, 0x8000);
+        load(&mut mapper, &mut memory, 0xA000, 0x0C);
+        mapper.cpu_write(&mut memory, 0x6000, 2);
+        assert_eq!(memory.prg_ram()[0x6000], 2);
+    }
+}
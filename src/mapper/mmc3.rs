@@ -0,0 +1,447 @@
+use crate::cartridge::cartridge_memory::CartridgeMemory;
+use crate::cartridge::header::Header;
+use crate::cartridge::mirroring::Mirroring;
+use crate::traits::mapper::Mapper;
+
+/// CPU cycles PPU A12 must stay low before a rise clocks the IRQ counter. The PPU drops A12
+/// briefly between the sprite pattern fetches of a scanline, and the MMC3 ignores those dips.
+const A12_FILTER_CYCLES: u64 = 3;
+
+/// The chips sold as mapper 4, which differ in the IRQ counter and the PRG-RAM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Revision {
+    /// MMC3B and MMC3C: the IRQ fires on every clock that leaves the counter at 0, so a latch
+    /// of 0 fires on every scanline.
+    Sharp,
+    /// MMC3A: the IRQ fires only when the counter reaches 0 by counting down from 1 or by a
+    /// reload requested through $C001.
+    Nec,
+    /// MMC6: the Sharp counter, with 1KB of PRG-RAM inside the chip at $7000-$7FFF whose two
+    /// halves are enabled separately.
+    Mmc6
+}
+
+/// Mapper 4: Nintendo's MMC3 on the TxROM boards, and the MMC6 on HKROM.
+///
+/// Writing $8000 selects one of eight bank registers and $8001 sets it. R0-R5 select the CHR
+/// banks, two of 2KB and four of 1KB, and R6 and R7 the two switchable 8KB PRG banks; the other
+/// two PRG slots hold the second-last and last banks. Bit 7 of $8000 swaps the halves of the
+/// pattern tables and bit 6 swaps the PRG slots at $8000 and $C000. $A000 sets the mirroring,
+/// $A001 protects the PRG-RAM, and $C000-$E001 run the IRQ counter.
+///
+/// The IRQ counter counts scanlines by watching PPU address line A12, which rises once per
+/// scanline when the background and sprites use different pattern tables. On each filtered
+/// rise the counter is reloaded from the latch if it is 0 or a reload was requested, and is
+/// otherwise decremented; the IRQ is raised when it ends up at 0, subject to the revision.
+/// NES 2.0 submapper 1 selects the MMC6 and submapper 4 the MMC3A.
+#[derive(Debug, Clone)]
+pub struct Mmc3 {
+    revision: Revision,
+    bank_select: u8,
+    banks: [u8; 8],
+    mirroring: Mirroring,
+    prg_ram_protect: u8,
+    irq_latch: u8,
+    irq_counter: u8,
+    irq_reload: bool,
+    irq_enabled: bool,
+    irq_pending: bool,
+    cycle: u64,
+    a12_low_since: Option<u64>
+}
+
+impl Mmc3 {
+    pub fn new(header: &Header) -> Self {
+        let revision = match header.submapper {
+            1 => Revision::Mmc6,
+            4 => Revision::Nec,
+            _ => Revision::Sharp,
+        };
+        Mmc3 {
+            revision,
+            bank_select: 0,
+            banks: [0, 2, 4, 5, 6, 7, 0, 1],
+            mirroring: header.mirroring,
+            // The register powers up in an unknown state. Enabling the RAM matches the MMC3
+            // boards games were tested on; MMC6 games enable each half before using it.
+            prg_ram_protect: if revision == Revision::Mmc6 { 0x00 } else { 0x80 },
+            irq_latch: 0,
+            irq_counter: 0,
+            irq_reload: false,
+            irq_enabled: false,
+            irq_pending: false,
+            cycle: 0,
+            a12_low_since: Some(0)
+        }
+    }
+
+    fn write_register(&mut self, address: u16, data: u8) {
+        let odd = address & 0x01 != 0;
+        match (address & 0xE000, odd) {
+            (0x8000, false) => self.bank_select = data,
+            (0x8000, true) => self.banks[(self.bank_select & 0x07) as usize] = data,
+            (0xA000, false) => {
+                // Four-screen boards wire the nametables to their own RAM instead.
+                if self.mirroring != Mirroring::FourScreen {
+                    self.mirroring = if data & 0x01 == 0 {
+                        Mirroring::Vertical
+                    } else {
+                        Mirroring::Horizontal
+                    };
+                }
+            },
+            (0xA000, true) => {
+                if self.revision != Revision::Mmc6 || self.mmc6_ram_enabled() {
+                    self.prg_ram_protect = data;
+                }
+            },
+            (0xC000, false) => self.irq_latch = data,
+            (0xC000, true) => {
+                self.irq_counter = 0;
+                self.irq_reload = true;
+            },
+            (_, false) => {
+                self.irq_enabled = false;
+                self.irq_pending = false;
+            },
+            (_, true) => self.irq_enabled = true,
+        }
+    }
+
+    /// The 8KB PRG-ROM bank at `address`.
+    fn prg_rom_bank(&self, memory: &CartridgeMemory, address: u16) -> usize {
+        let second_last = memory.prg_rom_banks(0x2000).saturating_sub(2);
+        let swapped = self.bank_select & 0x40 != 0;
+        match ((address >> 13) & 0x03, swapped) {
+            (0, false) | (2, true) => (self.banks[6] & 0x3F) as usize,
+            (0, true) | (2, false) => second_last,
+            (1, _) => (self.banks[7] & 0x3F) as usize,
+            _ => memory.prg_rom_banks(0x2000) - 1,
+        }
+    }
+
+    /// The 1KB CHR bank at `address`.
+    fn chr_bank(&self, address: u16) -> usize {
+        let address = if self.bank_select & 0x80 != 0 { address ^ 0x1000 } else { address };
+        let slot = (address >> 10) as usize;
+        match slot {
+            0..=3 => (self.banks[slot / 2] & 0xFE) as usize | (slot & 0x01),
+            _ => self.banks[slot - 2] as usize,
+        }
+    }
+
+    fn mmc6_ram_enabled(&self) -> bool {
+        self.bank_select & 0x20 != 0
+    }
+
+    /// Whether the CPU can read, and write, PRG-RAM at `address`.
+    fn prg_ram_access(&self, address: u16) -> (bool, bool) {
+        let protect = self.prg_ram_protect;
+        match self.revision {
+            Revision::Mmc6 => {
+                if !self.mmc6_ram_enabled() || address < 0x7000 {
+                    return (false, false);
+                }
+                // Bits 5 and 4 enable reads and writes of $7000-$71FF, bits 7 and 6 of
+                // $7200-$73FF. Writes need reads enabled too.
+                let shift = if address & 0x0200 == 0 { 4 } else { 6 };
+                let read = protect & (0x02 << shift) != 0;
+                (read, read && protect & (0x01 << shift) != 0)
+            },
+            _ => (protect & 0x80 != 0, protect & 0xC0 == 0x80),
+        }
+    }
+
+    fn cpu_read_prg_ram(&self, memory: &CartridgeMemory, address: u16) -> Option<u8> {
+        match self.prg_ram_access(address) {
+            (true, _) => memory.read_prg_ram(self.prg_ram_offset(address)),
+            // The MMC6 drives $00 for a disabled half while the other is readable.
+            (false, _) if self.revision == Revision::Mmc6
+                && address >= 0x7000
+                && self.mmc6_ram_enabled()
+                && self.prg_ram_protect & 0xA0 != 0 => Some(0),
+            _ => None,
+        }
+    }
+
+    fn prg_ram_offset(&self, address: u16) -> u16 {
+        match self.revision {
+            Revision::Mmc6 => address & 0x03FF,
+            _ => address - 0x6000,
+        }
+    }
+
+    /// Follows PPU A12, clocking the IRQ counter when it rises after being low long enough.
+    fn observe_ppu_address(&mut self, address: u16) {
+        let a12 = address & 0x1000 != 0;
+        match (a12, self.a12_low_since) {
+            (true, Some(since)) => {
+                self.a12_low_since = None;
+                if self.cycle.wrapping_sub(since) >= A12_FILTER_CYCLES {
+                    self.clock_irq_counter();
+                }
+            },
+            (false, None) => self.a12_low_since = Some(self.cycle),
+            _ => {},
+        }
+    }
+
+    fn clock_irq_counter(&mut self) {
+        let previous = self.irq_counter;
+        let reloaded = self.irq_reload;
+        if self.irq_counter == 0 || self.irq_reload {
+            self.irq_counter = self.irq_latch;
+        } else {
+            self.irq_counter -= 1;
+        }
+        self.irq_reload = false;
+
+        let fire = match self.revision {
+            Revision::Nec => self.irq_counter == 0 && (previous != 0 || reloaded),
+            _ => self.irq_counter == 0,
+        };
+        if fire && self.irq_enabled {
+            self.irq_pending = true;
+        }
+    }
+}
+
+impl Mapper for Mmc3 {
+    fn cpu_read_only(&self, memory: &CartridgeMemory, address: u16) -> Option<u8> {
+        match address {
+            0x6000..=0x7FFF => self.cpu_read_prg_ram(memory, address),
+            0x8000..=0xFFFF => Some(memory.read_prg_rom(self.prg_rom_bank(memory, address), 0x2000, address)),
+            _ => None,
+        }
+    }
+
+    fn cpu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        match address {
+            0x6000..=0x7FFF if self.prg_ram_access(address).1 => {
+                memory.write_prg_ram(self.prg_ram_offset(address), data);
+            },
+            0x8000..=0xFFFF => self.write_register(address, data),
+            _ => {},
+        }
+    }
+
+    fn ppu_read(&mut self, memory: &CartridgeMemory, address: u16) -> u8 {
+        self.observe_ppu_address(address);
+        self.ppu_read_only(memory, address)
+    }
+
+    fn ppu_read_only(&self, memory: &CartridgeMemory, address: u16) -> u8 {
+        memory.read_chr(self.chr_bank(address), 0x0400, address)
+    }
+
+    fn ppu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        self.observe_ppu_address(address);
+        memory.write_chr(self.chr_bank(address), 0x0400, address, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+
+    fn irq_line(&self) -> bool {
+        self.irq_pending
+    }
+
+    fn notify_cpu_cycle(&mut self) {
+        self.cycle = self.cycle.wrapping_add(1);
+    }
+
+    fn notify_ppu_address(&mut self, address: u16) {
+        self.observe_ppu_address(address);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapper::test_board::{banks, board};
+
+    fn mmc3(submapper: u8, prg_ram_size: usize) -> (Mmc3, CartridgeMemory) {
+        let prg_rom = banks(16, 0x2000);
+        let memory = CartridgeMemory::new(prg_rom, vec![0; prg_ram_size], banks(32, 0x0400), false);
+        board(Mmc3::new, 4, submapper, memory)
+    }
+
+    /// Holds A12 low for `cycles` CPU cycles, then raises it.
+    fn a12_rise(mapper: &mut Mmc3, cycles: u64) {
+        mapper.notify_ppu_address(0x0FF0);
+        for _ in 0..cycles {
+            mapper.notify_cpu_cycle();
+        }
+        mapper.notify_ppu_address(0x1000);
+    }
+
+    /// One scanline with the background at $0000 and sprites at $1000.
+    fn scanline(mapper: &mut Mmc3) {
+        a12_rise(mapper, 100);
+        mapper.notify_cpu_cycle();
+    }
+
+    /// Sets the IRQ latch, requests a reload and enables the IRQ.
+    fn start_irq(mapper: &mut Mmc3, memory: &mut CartridgeMemory, latch: u8) {
+        mapper.cpu_write(memory, 0xC000, latch);
+        mapper.cpu_write(memory, 0xC001, 0);
+        mapper.cpu_write(memory, 0xE001, 0);
+    }
+
+    fn acknowledge(mapper: &mut Mmc3, memory: &mut CartridgeMemory) {
+        mapper.cpu_write(memory, 0xE000, 0);
+        mapper.cpu_write(memory, 0xE001, 0);
+    }
+
+    #[test]
+    fn prg_and_chr_banks() {
+        let (mut mapper, mut memory) = mmc3(0, 0x2000);
+        mapper.cpu_write(&mut memory, 0x8000, 6);
+        mapper.cpu_write(&mut memory, 0x8001, 5);
+        mapper.cpu_write(&mut memory, 0x8000, 7);
+        mapper.cpu_write(&mut memory, 0x8001, 9);
+        let prg = |mapper: &Mmc3, memory: &CartridgeMemory| {
+            [0x8000, 0xA000, 0xC000, 0xE000].map(|address| mapper.cpu_read_only(memory, address).unwrap())
+        };
+        assert_eq!(prg(&mapper, &memory), [5, 9, 14, 15]);
+        mapper.cpu_write(&mut memory, 0x8000, 0x40);
+        assert_eq!(prg(&mapper, &memory), [14, 9, 5, 15]);
+
+        mapper.cpu_write(&mut memory, 0x8000, 0x00);
+        mapper.cpu_write(&mut memory, 0x8001, 9);
+        mapper.cpu_write(&mut memory, 0x8000, 0x05);
+        mapper.cpu_write(&mut memory, 0x8001, 20);
+        let chr = |mapper: &Mmc3, memory: &CartridgeMemory, addresses: [u16; 3]| {
+            addresses.map(|address| mapper.ppu_read_only(memory, address))
+        };
+        assert_eq!(chr(&mapper, &memory, [0x0000, 0x0400, 0x1C00]), [8, 9, 20]);
+        mapper.cpu_write(&mut memory, 0x8000, 0x80);
+        assert_eq!(chr(&mapper, &memory, [0x1000, 0x1400, 0x0C00]), [8, 9, 20]);
+    }
+
+    #[test]
+    fn mirroring_and_prg_ram_protect() {
+        let (mut mapper, mut memory) = mmc3(0, 0x2000);
+        mapper.cpu_write(&mut memory, 0xA000, 0);
+        assert_eq!(mapper.mirroring(), Mirroring::Vertical);
+        mapper.cpu_write(&mut memory, 0xA000, 1);
+        assert_eq!(mapper.mirroring(), Mirroring::Horizontal);
+
+        mapper.cpu_write(&mut memory, 0x6000, 5);
+        mapper.cpu_write(&mut memory, 0xA001, 0xC0);
+        mapper.cpu_write(&mut memory, 0x6000, 6);
+        assert_eq!(mapper.cpu_read(&memory, 0x6000), Some(5));
+        mapper.cpu_write(&mut memory, 0xA001, 0x00);
+        assert_eq!(mapper.cpu_read(&memory, 0x6000), None);
+    }
+
+    #[test]
+    fn short_a12_dips_are_filtered() {
+        let (mut mapper, mut memory) = mmc3(0, 0);
+        start_irq(&mut mapper, &mut memory, 1);
+        a12_rise(&mut mapper, 2);
+        assert_eq!(mapper.irq_counter, 0);
+        assert!(mapper.irq_reload);
+        a12_rise(&mut mapper, 3);
+        assert_eq!(mapper.irq_counter, 1);
+        // A12 staying high, or the PPU reading $1xxx again, is not another rise.
+        mapper.notify_ppu_address(0x1FF0);
+        mapper.notify_cpu_cycle();
+        mapper.notify_ppu_address(0x1008);
+        assert_eq!(mapper.irq_counter, 1);
+    }
+
+    #[test]
+    fn counter_reloads_from_the_latch() {
+        let (mut mapper, mut memory) = mmc3(0, 0);
+        start_irq(&mut mapper, &mut memory, 2);
+        scanline(&mut mapper);
+        assert_eq!(mapper.irq_counter, 2);
+        scanline(&mut mapper);
+        assert!(!mapper.irq_line());
+        scanline(&mut mapper);
+        assert!(mapper.irq_line());
+        acknowledge(&mut mapper, &mut memory);
+        assert!(!mapper.irq_line());
+        // At zero the counter reloads on its own.
+        scanline(&mut mapper);
+        assert_eq!(mapper.irq_counter, 2);
+        assert!(!mapper.irq_line());
+
+        // $C001 forces a reload on the next clock, and a new latch value is picked up then.
+        scanline(&mut mapper);
+        mapper.cpu_write(&mut memory, 0xC000, 5);
+        mapper.cpu_write(&mut memory, 0xC001, 0);
+        scanline(&mut mapper);
+        assert_eq!(mapper.irq_counter, 5);
+    }
+
+    #[test]
+    fn disabled_irq_is_not_raised() {
+        let (mut mapper, mut memory) = mmc3(0, 0);
+        start_irq(&mut mapper, &mut memory, 1);
+        mapper.cpu_write(&mut memory, 0xE000, 0);
+        scanline(&mut mapper);
+        scanline(&mut mapper);
+        assert_eq!(mapper.irq_counter, 0);
+        assert!(!mapper.irq_line());
+    }
+
+    #[test]
+    fn zero_latch_fires_every_scanline_on_sharp_and_once_on_nec() {
+        for (submapper, expected) in [(0, [true, true, true]), (4, [true, false, false])] {
+            let (mut mapper, mut memory) = mmc3(submapper, 0);
+            start_irq(&mut mapper, &mut memory, 0);
+            for fired in expected {
+                scanline(&mut mapper);
+                assert_eq!(mapper.irq_line(), fired, "submapper {}", submapper);
+                acknowledge(&mut mapper, &mut memory);
+            }
+        }
+    }
+
+    #[test]
+    fn nec_fires_when_counting_down_to_zero() {
+        let (mut mapper, mut memory) = mmc3(4, 0);
+        start_irq(&mut mapper, &mut memory, 1);
+        scanline(&mut mapper);
+        assert!(!mapper.irq_line());
+        scanline(&mut mapper);
+        assert!(mapper.irq_line());
+    }
+
+    #[test]
+    fn mmc6_prg_ram_enable_and_protect() {
+        let (mut mapper, mut memory) = mmc3(1, 0x0400);
+        // $A001 is ignored and the RAM is off until $8000 bit 5 enables it.
+        mapper.cpu_write(&mut memory, 0xA001, 0xF0);
+        mapper.cpu_write(&mut memory, 0x7000, 1);
+        assert_eq!(mapper.cpu_read(&memory, 0x7000), None);
+        mapper.cpu_write(&mut memory, 0x8000, 0x20);
+        assert_eq!(mapper.cpu_read(&memory, 0x7000), None);
+
+        // Low half readable and writable, high half disabled.
+        mapper.cpu_write(&mut memory, 0xA001, 0x30);
+        mapper.cpu_write(&mut memory, 0x7000, 1);
+        mapper.cpu_write(&mut memory, 0x7200, 2);
+        assert_eq!(mapper.cpu_read(&memory, 0x7000), Some(1));
+        assert_eq!(mapper.cpu_read(&memory, 0x7400), Some(1));
+        assert_eq!(mapper.cpu_read(&memory, 0x7200), Some(0));
+        assert_eq!(mapper.cpu_read(&memory, 0x6000), None);
+
+        // High half readable but write-protected.
+        mapper.cpu_write(&mut memory, 0xA001, 0xB0);
+        mapper.cpu_write(&mut memory, 0x7200, 3);
+        assert_eq!(mapper.cpu_read(&memory, 0x7200), Some(0));
+        mapper.cpu_write(&mut memory, 0xA001, 0xF0);
+        mapper.cpu_write(&mut memory, 0x7200, 3);
+        assert_eq!(mapper.cpu_read(&memory, 0x7200), Some(3));
+
+        // Write enable without read enable does nothing.
+        mapper.cpu_write(&mut memory, 0xA001, 0x10);
+        mapper.cpu_write(&mut memory, 0x7000, 9);
+        mapper.cpu_write(&mut memory, 0xA001, 0x20);
+        assert_eq!(mapper.cpu_read(&memory, 0x7000), Some(1));
+    }
+}
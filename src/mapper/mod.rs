@@ -0,0 +1,8 @@
+pub mod axrom;
+pub mod cnrom;
+pub mod mmc1;
+pub mod mmc3;
+pub mod nrom;
+#[cfg(test)]
+mod test_board;
+pub mod uxrom;
\ No newline at end of file
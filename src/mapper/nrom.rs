@@ -0,0 +1,72 @@
+use crate::cartridge::cartridge_memory::CartridgeMemory;
+use crate::cartridge::header::Header;
+use crate::cartridge::mirroring::Mirroring;
+use crate::traits::mapper::Mapper;
+
+/// Mapper 0: no bank switching. 16KB or 32KB of PRG-ROM at $8000-$FFFF, the smaller size
+/// mirrored to fill it, and 8KB of CHR. Family BASIC adds PRG-RAM at $6000-$7FFF.
+#[derive(Debug, Clone)]
+pub struct Nrom {
+    mirroring: Mirroring
+}
+
+impl Nrom {
+    pub fn new(header: &Header) -> Self {
+        Nrom {
+            mirroring: header.mirroring
+        }
+    }
+}
+
+impl Mapper for Nrom {
+    fn cpu_read_only(&self, memory: &CartridgeMemory, address: u16) -> Option<u8> {
+        match address {
+            0x6000..=0x7FFF => memory.read_prg_ram(address - 0x6000),
+            0x8000..=0xFFFF => Some(memory.read_prg_rom(0, 0x8000, address - 0x8000)),
+            _ => None,
+        }
+    }
+
+    fn cpu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        if let 0x6000..=0x7FFF = address {
+            memory.write_prg_ram(address - 0x6000, data);
+        }
+    }
+
+    fn ppu_read_only(&self, memory: &CartridgeMemory, address: u16) -> u8 {
+        memory.read_chr(0, 0x2000, address)
+    }
+
+    fn ppu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        memory.write_chr(0, 0x2000, address, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapper::test_board::{banks, board, rom_memory};
+
+    #[test]
+    fn mirrors_16kb_of_prg_rom_at_c000() {
+        let mut prg_rom = vec![0; 0x4000];
+        prg_rom[0x0000] = 0x12;
+        prg_rom[0x3FFF] = 0x34;
+        let (mut mapper, memory) = board(Nrom::new, 0, 0, rom_memory(prg_rom, vec![0; 0x2000], true));
+        assert_eq!(mapper.cpu_read(&memory, 0x8000), Some(0x12));
+        assert_eq!(mapper.cpu_read(&memory, 0xC000), Some(0x12));
+        assert_eq!(mapper.cpu_read(&memory, 0xBFFF), Some(0x34));
+        assert_eq!(mapper.cpu_read(&memory, 0xFFFF), Some(0x34));
+    }
+
+    #[test]
+    fn maps_32kb_of_prg_rom_without_mirroring() {
+        let (mut mapper, memory) = board(Nrom::new, 0, 0, rom_memory(banks(2, 0x4000), vec![0; 0x2000], true));
+        assert_eq!(mapper.cpu_read(&memory, 0x8000), Some(0));
+        assert_eq!(mapper.cpu_read(&memory, 0xC000), Some(1));
+    }
+}
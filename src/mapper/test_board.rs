@@ -0,0 +1,46 @@
+use crate::cartridge::cartridge_memory::CartridgeMemory;
+use crate::cartridge::console_type::ConsoleType;
+use crate::cartridge::header::Header;
+use crate::cartridge::header_format::HeaderFormat;
+use crate::cartridge::mirroring::Mirroring;
+use crate::cartridge::timing::Timing;
+
+/// A header for a board with the given mapper and submapper and horizontal mirroring. Mappers
+/// only look at those fields; the memory sizes come from the `CartridgeMemory` instead.
+pub(crate) fn header(mapper: u16, submapper: u8) -> Header {
+    Header {
+        format: HeaderFormat::Nes20,
+        prg_rom_size: 0,
+        chr_rom_size: 0,
+        prg_ram_size: 0,
+        prg_nvram_size: 0,
+        chr_ram_size: 0,
+        chr_nvram_size: 0,
+        mapper,
+        submapper,
+        mirroring: Mirroring::Horizontal,
+        battery: false,
+        trainer: false,
+        timing: Timing::Ntsc,
+        console_type: ConsoleType::Nes,
+        misc_roms: 0,
+        default_expansion_device: 0,
+    }
+}
+
+/// `count` banks of `size` bytes, each filled with its bank number, so that a read shows which
+/// bank is mapped.
+pub(crate) fn banks(count: usize, size: usize) -> Vec<u8> {
+    (0..count).flat_map(|bank| vec![bank as u8; size]).collect()
+}
+
+/// A board built by `new` from the header for `mapper` and `submapper`, with `memory` plugged
+/// in, e.g. `board(Uxrom::new, 2, 0, memory)`.
+pub(crate) fn board<M>(new: fn(&Header) -> M, mapper: u16, submapper: u8, memory: CartridgeMemory) -> (M, CartridgeMemory) {
+    (new(&header(mapper, submapper)), memory)
+}
+
+/// `prg_rom` and `chr` with no PRG-RAM, as on the discrete-logic boards.
+pub(crate) fn rom_memory(prg_rom: Vec<u8>, chr: Vec<u8>, chr_is_ram: bool) -> CartridgeMemory {
+    CartridgeMemory::new(prg_rom, Vec::new(), chr, chr_is_ram)
+}
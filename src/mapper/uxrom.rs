@@ -0,0 +1,93 @@
+use crate::cartridge::cartridge_memory::CartridgeMemory;
+use crate::cartridge::header::Header;
+use crate::cartridge::mirroring::Mirroring;
+use crate::traits::mapper::Mapper;
+
+/// Mapper 2: a switchable 16KB PRG-ROM bank at $8000-$BFFF and the last bank fixed at
+/// $C000-$FFFF, with 8KB of CHR-RAM. Writing anywhere in $8000-$FFFF selects the bank.
+///
+/// The ROM keeps driving the data bus during the write, so the register receives the AND of
+/// the written value and the ROM byte at that address. Games write a value equal to the ROM
+/// byte to avoid this. NES 2.0 submapper 1 marks boards without the conflict.
+#[derive(Debug, Clone)]
+pub struct Uxrom {
+    bank: u8,
+    bus_conflicts: bool,
+    mirroring: Mirroring
+}
+
+impl Uxrom {
+    pub fn new(header: &Header) -> Self {
+        Uxrom {
+            bank: 0,
+            bus_conflicts: header.submapper != 1,
+            mirroring: header.mirroring
+        }
+    }
+}
+
+impl Mapper for Uxrom {
+    fn cpu_read_only(&self, memory: &CartridgeMemory, address: u16) -> Option<u8> {
+        match address {
+            0x6000..=0x7FFF => memory.read_prg_ram(address - 0x6000),
+            0x8000..=0xBFFF => Some(memory.read_prg_rom(self.bank as usize, 0x4000, address)),
+            0xC000..=0xFFFF => Some(memory.read_prg_rom(memory.prg_rom_banks(0x4000) - 1, 0x4000, address)),
+            _ => None,
+        }
+    }
+
+    fn cpu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        match address {
+            0x6000..=0x7FFF => memory.write_prg_ram(address - 0x6000, data),
+            0x8000..=0xFFFF => {
+                self.bank = if self.bus_conflicts {
+                    data & self.cpu_read_only(memory, address).unwrap_or(0xFF)
+                } else {
+                    data
+                };
+            },
+            _ => {},
+        }
+    }
+
+    fn ppu_read_only(&self, memory: &CartridgeMemory, address: u16) -> u8 {
+        memory.read_chr(0, 0x2000, address)
+    }
+
+    fn ppu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8) {
+        memory.write_chr(0, 0x2000, address, data);
+    }
+
+    fn mirroring(&self) -> Mirroring {
+        self.mirroring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapper::test_board::{banks, board, rom_memory};
+
+    fn uxrom(submapper: u8) -> (Uxrom, CartridgeMemory) {
+        board(Uxrom::new, 2, submapper, rom_memory(banks(8, 0x4000), vec![0; 0x2000], true))
+    }
+
+    #[test]
+    fn switches_8000_and_fixes_the_last_bank() {
+        let (mut mapper, mut memory) = uxrom(1);
+        assert_eq!(mapper.cpu_read(&memory, 0x8000), Some(0));
+        assert_eq!(mapper.cpu_read(&memory, 0xFFFF), Some(7));
+        mapper.cpu_write(&mut memory, 0x8000, 5);
+        assert_eq!(mapper.cpu_read(&memory, 0xBFFF), Some(5));
+        assert_eq!(mapper.cpu_read(&memory, 0xC000), Some(7));
+    }
+
+    #[test]
+    fn bus_conflict_ands_the_value_with_the_rom() {
+        let (mut mapper, mut memory) = uxrom(0);
+        mapper.cpu_write(&mut memory, 0xC000, 0x0E);
+        assert_eq!(mapper.cpu_read(&memory, 0x8000), Some(6));
+        mapper.cpu_write(&mut memory, 0x8000, 0x05);
+        assert_eq!(mapper.cpu_read(&memory, 0x8000), Some(4));
+    }
+}
@@ -0,0 +1,32 @@
+use crate::cpu::emulation_error::EmulationError;
+use crate::traits::read::Read;
+use crate::traits::write::Write;
+use std::fmt::Debug;
+
+/// Everything the CPU is wired to: the address and data buses plus the interrupt inputs.
+/// Implemented by the NES `Bus`, and by simpler machines such as a flat 64KB memory when the
+/// core is used on its own.
+pub trait CpuBus: Read<u16, u8> + Write<u16, u8> + Debug {
+
+    /// Level of the shared /IRQ input, true while any device asserts it.
+    fn irq_line(&self) -> bool {
+        false
+    }
+
+    /// Level of the /NMI input, true while asserted.
+    fn nmi_line(&self) -> bool {
+        false
+    }
+
+    /// Called by the CPU at the end of every cycle, so that devices which count CPU cycles
+    /// stay in step with it.
+    fn tick(&mut self) {}
+
+    /// Writes `data`, or reports that no device could accept it. The CPU writes through this
+    /// rather than `Write::write`. By default every write succeeds; a bus whose writes can
+    /// fail returns `EmulationError::BusFault`, which stops the instruction.
+    fn try_write(&mut self, address: u16, data: u8) -> Result<(), EmulationError> {
+        self.write(address, data);
+        Ok(())
+    }
+}
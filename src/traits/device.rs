@@ -0,0 +1,41 @@
+use crate::traits::read::Read;
+use crate::traits::write::Write;
+use std::fmt::Debug;
+
+/// Anything that can be mapped into the bus address space: RAM, the PPU and APU registers,
+/// controllers, cartridges, or a port a test program writes its output to.
+///
+/// The bus hands a device the address left after applying the mask it was mapped with, so a
+/// device only has to decode the addresses it actually sees. Returning `None` from `read`
+/// means the device does not drive the data bus for that address, and the read sees open bus.
+/// `read_only` is a peek with no side effects on the device, as `Read` describes: a mapper's
+/// IRQ counter must not tick and a controller must not shift because a debugger looked at it.
+///
+/// Devices are owned by the bus, so they must be `Send` for a console to move between threads
+/// and cloneable for it to be snapshotted. Any `Clone` device gets `box_clone` for free.
+pub trait Device: Read<u16, u8> + Write<u16, u8> + Debug + Send + DeviceClone {
+
+    /// The data lines the device drives when it answers a read of `address`. The others float
+    /// and keep the value last seen on the bus: the controller ports at $4016/$4017 only drive
+    /// the low five bits, and PPU status only the top three.
+    fn driven_bits(&self, _address: u16) -> u8 {
+        0xFF
+    }
+}
+
+/// Clones a device behind a trait object.
+pub trait DeviceClone {
+    fn box_clone(&self) -> Box<dyn Device>;
+}
+
+impl<T: Device + Clone + 'static> DeviceClone for T {
+    fn box_clone(&self) -> Box<dyn Device> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Device> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
@@ -0,0 +1,67 @@
+use crate::cartridge::cartridge_memory::CartridgeMemory;
+use crate::cartridge::mirroring::Mirroring;
+use std::fmt::Debug;
+
+/// The bank switching logic of a cartridge board. The cartridge owns the ROM and RAM and hands
+/// them to the mapper on every access; the mapper holds the board's registers and decides what
+/// each CPU address in $4020-$FFFF and PPU address in $0000-$1FFF reaches.
+///
+/// As with `Read`, the `_only` methods are side-effect-free peeks, and the others are the
+/// accesses the hardware actually makes. Most boards only react to writes, so the reads
+/// default to the peeks.
+pub trait Mapper: Debug + Send + MapperClone {
+
+    fn cpu_read(&mut self, memory: &CartridgeMemory, address: u16) -> Option<u8> {
+        self.cpu_read_only(memory, address)
+    }
+
+    /// What the cartridge drives onto the CPU data bus for `address`, or `None` for open bus.
+    fn cpu_read_only(&self, memory: &CartridgeMemory, address: u16) -> Option<u8>;
+
+    fn cpu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8);
+
+    fn ppu_read(&mut self, memory: &CartridgeMemory, address: u16) -> u8 {
+        self.ppu_read_only(memory, address)
+    }
+
+    fn ppu_read_only(&self, memory: &CartridgeMemory, address: u16) -> u8;
+
+    fn ppu_write(&mut self, memory: &mut CartridgeMemory, address: u16, data: u8);
+
+    /// How the nametables are currently arranged.
+    fn mirroring(&self) -> Mirroring;
+
+    /// Whether the mapper is holding the CPU's /IRQ line low.
+    fn irq_line(&self) -> bool {
+        false
+    }
+
+    /// Called at the end of each visible and pre-render scanline while rendering is enabled,
+    /// for boards that count scanlines.
+    fn notify_scanline(&mut self) {}
+
+    /// Called once per CPU cycle, for boards that count them.
+    fn notify_cpu_cycle(&mut self) {}
+
+    /// Called with each address the PPU puts on its bus, nametable and palette fetches included,
+    /// for boards that watch the PPU address lines. Pattern table accesses through `ppu_read`
+    /// and `ppu_write` are not repeated here.
+    fn notify_ppu_address(&mut self, _address: u16) {}
+}
+
+/// Clones a mapper behind a trait object.
+pub trait MapperClone {
+    fn box_clone(&self) -> Box<dyn Mapper>;
+}
+
+impl<T: Mapper + Clone + 'static> MapperClone for T {
+    fn box_clone(&self) -> Box<dyn Mapper> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Mapper> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
@@ -1,2 +1,5 @@
+pub mod cpu_bus;
+pub mod device;
+pub mod mapper;
 pub mod read;
 pub mod write;
\ No newline at end of file
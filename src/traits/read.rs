@@ -1,6 +1,15 @@
 pub trait Read<T, U> {
 
-    fn read(&self, address: T) -> Option<U>;
+    /// Performs a read as the CPU would, with whatever side effects the hardware has: reading
+    /// PPU status clears the vblank flag, reading $2007 advances the VRAM address, reading a
+    /// controller shifts out its next button. `None` means nothing drives the data bus.
+    fn read(&mut self, address: T) -> Option<U>;
 
+    /// Returns what `read` would return at this moment without changing any state, so that
+    /// debuggers, memory viewers and tracers can inspect memory and registers while the
+    /// emulation runs. Implementations must not reach for interior mutability to update
+    /// latches, counters or buffers here: two calls in a row return the same value, and a
+    /// following `read` behaves exactly as if the peek had not happened. Where the value a read
+    /// would produce cannot be known without performing it, return `None` rather than guess.
     fn read_only(&self, address: T) -> Option<U>;
 }
\ No newline at end of file
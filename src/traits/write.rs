@@ -1,4 +1,4 @@
 pub trait Write<T, U> {
 
-    fn write(&self, address: T, data: U);
+    fn write(&mut self, address: T, data: U);
 }
\ No newline at end of file
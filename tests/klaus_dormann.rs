@@ -0,0 +1,88 @@
+//! Runs Klaus Dormann's 6502 functional and decimal tests
+//! (https://github.com/Klaus2m5/6502_65C02_functional_tests) on a bare NMOS 6502 with 64KB of
+//! RAM. Both programs signal their result by jumping to themselves.
+//!
+//! The binaries are not checked in. Build them with their default configuration, put
+//! `6502_functional_test.bin` (a full 64KB image) and `6502_decimal_test.bin` (assembled at
+//! $0200) in `tests/klaus_dormann/`, or point `KLAUS_DORMANN_TESTS` at another directory, and
+//! run `cargo test --test klaus_dormann -- --ignored`. The tests are ignored by default because
+//! of this, and fail if their binary is missing.
+
+use rust_nes_emulator::bus::flat_memory::FlatMemory;
+use rust_nes_emulator::cpu::cpu_6502::Cpu6502;
+use rust_nes_emulator::cpu::variant::Variant;
+use rust_nes_emulator::traits::read::Read;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Far more than either test needs; hitting it means the CPU is stuck in a loop that is not a
+/// trap.
+const INSTRUCTION_LIMIT: u64 = 200_000_000;
+
+const FUNCTIONAL_TEST_START: u16 = 0x0400;
+const FUNCTIONAL_TEST_SUCCESS: u16 = 0x3469;
+/// Where the functional test keeps the number of the test it is running.
+const FUNCTIONAL_TEST_CASE: u16 = 0x0200;
+
+const DECIMAL_TEST_START: u16 = 0x0200;
+/// The decimal test leaves 0 here on success and 1 on failure.
+const DECIMAL_TEST_ERROR: u16 = 0x000B;
+
+fn load_binary(file: &str) -> Vec<u8> {
+    let path = env::var_os("KLAUS_DORMANN_TESTS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("klaus_dormann"))
+        .join(file);
+    fs::read(&path).unwrap_or_else(|error| {
+        panic!(
+            "Failed to read {}: {}. Assemble it from https://github.com/Klaus2m5/6502_65C02_functional_tests \
+             or set KLAUS_DORMANN_TESTS",
+            path.display(), error
+        )
+    })
+}
+
+/// Runs from `start` until an instruction jumps to itself, returning the address of the trap.
+fn run_until_trap(memory: &mut FlatMemory, start: u16) -> u16 {
+    let mut cpu = Cpu6502::new();
+    cpu.set_variant(Variant::Nmos6502);
+    cpu.set_stack_pointer(0xFF);
+    cpu.set_program_counter(start);
+
+    let mut previous_program_counter = None;
+    let mut instructions = 0;
+    cpu.run_until(memory, |cpu, _| {
+        let program_counter = cpu.program_counter();
+        let trapped = previous_program_counter == Some(program_counter);
+        previous_program_counter = Some(program_counter);
+        instructions += 1;
+        assert!(instructions <= INSTRUCTION_LIMIT, "No trap after {} instructions, PC ${:04X}", INSTRUCTION_LIMIT, program_counter);
+        trapped
+    }).unwrap_or_else(|error| panic!("CPU stopped: {}", error));
+    cpu.program_counter()
+}
+
+#[test]
+#[ignore = "needs Klaus Dormann's test binaries, see the top of this file"]
+fn functional_test() {
+    let binary = load_binary("6502_functional_test.bin");
+    let mut memory = FlatMemory::new();
+    memory.load(0x0000, &binary);
+
+    let trap = run_until_trap(&mut memory, FUNCTIONAL_TEST_START);
+    let test_case = memory.read_only(FUNCTIONAL_TEST_CASE).unwrap_or(0);
+    assert_eq!(trap, FUNCTIONAL_TEST_SUCCESS, "Functional test trapped at ${:04X} in test case ${:02X}", trap, test_case);
+}
+
+#[test]
+#[ignore = "needs Klaus Dormann's test binaries, see the top of this file"]
+fn decimal_test() {
+    let binary = load_binary("6502_decimal_test.bin");
+    let mut memory = FlatMemory::new();
+    memory.load(DECIMAL_TEST_START, &binary);
+
+    let trap = run_until_trap(&mut memory, DECIMAL_TEST_START);
+    let error = memory.read_only(DECIMAL_TEST_ERROR).unwrap_or(0);
+    assert_eq!(error, 0, "Decimal test trapped at ${:04X} with ERROR = {}", trap, error);
+}
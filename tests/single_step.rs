@@ -0,0 +1,212 @@
+//! Runs the per-opcode single-step test vectors published at https://github.com/SingleStepTests
+//! against `Cpu6502`. Each case gives the registers and RAM before and after one instruction,
+//! plus every bus access the instruction makes.
+//!
+//! The vectors are too large to check in, so the tests are ignored by default. Clone
+//! https://github.com/SingleStepTests/65x02, copy the JSON files of each set (`nes6502`, `6502`,
+//! `wdc65c02`) from its `<set>/v1/` into `tests/single_step/<set>/`, or point
+//! `SINGLE_STEP_TESTS` at a directory laid out the same way, and run
+//! `cargo test --test single_step -- --ignored`. A set that is not present fails.
+
+use rust_nes_emulator::cpu::cpu_6502::Cpu6502;
+use rust_nes_emulator::cpu::cpu_state::CpuState;
+use rust_nes_emulator::cpu::processor_status_register::ProcessorStatusRegister;
+use rust_nes_emulator::cpu::variant::Variant;
+use rust_nes_emulator::traits::cpu_bus::CpuBus;
+use rust_nes_emulator::traits::read::Read;
+use rust_nes_emulator::traits::write::Write;
+use serde::Deserialize;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bits 4 and 5 of P only exist in copies pushed to the stack, so they are not compared.
+const STATUS_MASK: u8 = 0xCF;
+
+#[derive(Debug, Deserialize)]
+struct TestCase {
+    name: String,
+    initial: MachineState,
+    #[serde(rename = "final")]
+    expected: MachineState,
+    cycles: Vec<BusCycle>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MachineState {
+    pc: u16,
+    s: u8,
+    a: u8,
+    x: u8,
+    y: u8,
+    p: u8,
+    ram: Vec<(u16, u8)>,
+}
+
+/// One entry of a bus log: address, data and `"read"` or `"write"`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct BusCycle(u16, u8, String);
+
+impl fmt::Display for BusCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04X} {:02X} {:<5}", self.0, self.1, self.2)
+    }
+}
+
+/// 64KB of RAM that records every access the CPU makes.
+#[derive(Debug)]
+struct TestBus {
+    memory: Vec<u8>,
+    log: Vec<BusCycle>,
+}
+
+impl TestBus {
+    fn new() -> Self {
+        TestBus {
+            memory: vec![0; 0x10000],
+            log: Vec::new(),
+        }
+    }
+}
+
+impl CpuBus for TestBus {}
+
+impl Read<u16, u8> for TestBus {
+    fn read(&mut self, address: u16) -> Option<u8> {
+        let data = self.memory[address as usize];
+        self.log.push(BusCycle(address, data, "read".to_string()));
+        Some(data)
+    }
+
+    fn read_only(&self, address: u16) -> Option<u8> {
+        Some(self.memory[address as usize])
+    }
+}
+
+impl Write<u16, u8> for TestBus {
+    fn write(&mut self, address: u16, data: u8) {
+        self.memory[address as usize] = data;
+        self.log.push(BusCycle(address, data, "write".to_string()));
+    }
+}
+
+fn run_case(case: &TestCase, variant: Variant) -> Result<(), String> {
+    let mut bus = TestBus::new();
+    for &(address, data) in &case.initial.ram {
+        bus.memory[address as usize] = data;
+    }
+    let mut cpu = Cpu6502::new();
+    cpu.set_variant(variant);
+    cpu.set_state(&CpuState {
+        accumulator: case.initial.a,
+        x_register: case.initial.x,
+        y_register: case.initial.y,
+        stack_pointer: case.initial.s,
+        program_counter: case.initial.pc,
+        status_register: ProcessorStatusRegister::from_bits(case.initial.p),
+    });
+    let mut mismatches = Vec::new();
+    if let Err(error) = cpu.step_instruction(&mut bus) {
+        mismatches.push(format!("CPU error: {}", error));
+    }
+    let registers = [
+        ("PC", case.expected.pc, cpu.program_counter()),
+        ("S", case.expected.s as u16, cpu.stack_pointer() as u16),
+        ("A", case.expected.a as u16, cpu.accumulator() as u16),
+        ("X", case.expected.x as u16, cpu.x_register() as u16),
+        ("Y", case.expected.y as u16, cpu.y_register() as u16),
+        ("P", (case.expected.p & STATUS_MASK) as u16, (cpu.status().bits() & STATUS_MASK) as u16),
+    ];
+    for (register, expected, actual) in registers {
+        if expected != actual {
+            mismatches.push(format!("{}: expected ${:02X}, got ${:02X}", register, expected, actual));
+        }
+    }
+    for &(address, expected) in &case.expected.ram {
+        let actual = bus.memory[address as usize];
+        if expected != actual {
+            mismatches.push(format!("RAM ${:04X}: expected ${:02X}, got ${:02X}", address, expected, actual));
+        }
+    }
+    if bus.log != case.cycles {
+        mismatches.push(format!("bus activity (expected | actual):\n{}", bus_log_comparison(&case.cycles, &bus.log)));
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("\"{}\"\n  {}", case.name, mismatches.join("\n  ")))
+    }
+}
+
+/// The two logs side by side, one cycle per line, with differing cycles marked.
+fn bus_log_comparison(expected: &[BusCycle], actual: &[BusCycle]) -> String {
+    let format_cycle = |cycle: Option<&BusCycle>| cycle.map_or("-".to_string(), |cycle| cycle.to_string());
+    (0..expected.len().max(actual.len()))
+        .map(|index| {
+            let marker = if expected.get(index) == actual.get(index) { "" } else { "  <--" };
+            format!("    {:<16} | {:<16}{}", format_cycle(expected.get(index)), format_cycle(actual.get(index)), marker)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn suite_directory(set: &str) -> PathBuf {
+    env::var_os("SINGLE_STEP_TESTS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("single_step"))
+        .join(set)
+}
+
+/// Runs every opcode file in the set, reporting each failing opcode with the number of failed
+/// cases and the details of the first one.
+fn run_suite(set: &str, variant: Variant) {
+    let directory = suite_directory(set);
+    assert!(
+        directory.is_dir(),
+        "{} not found: copy the {} vectors from https://github.com/SingleStepTests/65x02 there, \
+         or set SINGLE_STEP_TESTS",
+        directory.display(), set
+    );
+
+    let mut files = fs::read_dir(&directory)
+        .expect("Failed to list single-step test directory")
+        .map(|entry| entry.expect("Failed to read directory entry").path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut report = Vec::new();
+    for path in &files {
+        let text = fs::read_to_string(path).expect("Failed to read test file");
+        let cases: Vec<TestCase> = serde_json::from_str(&text).expect("Failed to parse test file");
+        let failures = cases.iter()
+            .filter_map(|case| run_case(case, variant).err())
+            .collect::<Vec<_>>();
+        if let Some(first) = failures.first() {
+            let opcode = path.file_stem().unwrap_or_default().to_string_lossy();
+            report.push(format!("opcode {}: {} of {} cases failed, first: {}", opcode, failures.len(), cases.len(), first));
+        }
+    }
+
+    assert!(report.is_empty(), "{} of {} opcodes failed\n\n{}", report.len(), files.len(), report.join("\n\n"));
+}
+
+#[test]
+#[ignore = "needs the SingleStepTests vectors, see the top of this file"]
+fn nes6502() {
+    run_suite("nes6502", Variant::Ricoh2A03);
+}
+
+#[test]
+#[ignore = "needs the SingleStepTests vectors, see the top of this file"]
+fn nmos6502() {
+    run_suite("6502", Variant::Nmos6502);
+}
+
+#[test]
+#[ignore = "needs the SingleStepTests vectors, see the top of this file"]
+fn wdc65c02() {
+    run_suite("wdc65c02", Variant::Wdc65C02);
+}